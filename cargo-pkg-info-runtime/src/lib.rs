@@ -0,0 +1,39 @@
+//! Runtime companion to `cargo-pkg-info-struct-builder`.
+//!
+//! Every package that uses the builder gets its own `CargoPkgInfo` type. When
+//! the build script enables `BuildMetadataInjector::with_package_info_trait`,
+//! the generated struct implements [`PackageInfo`], so that shared libraries
+//! (logging, telemetry, `/about` endpoints) can be written once against the
+//! trait:
+//!
+//! ```
+//! use cargo_pkg_info_runtime::PackageInfo;
+//!
+//! fn startup_banner(info: &dyn PackageInfo) -> String {
+//!     format!("{} v{}", info.name(), info.version())
+//! }
+//! ```
+//!
+//! The application then passes its own metadata, e.g.
+//! `startup_banner(&CargoPkgInfo {})`.
+
+/// Package metadata captured at build time.
+pub trait PackageInfo {
+    /// Returns the package name.
+    fn name(&self) -> &'static str;
+
+    /// Returns the package version, e.g. `1.2.3-beta`.
+    fn version(&self) -> &'static str;
+
+    /// Returns the package authors, separated by `:` as Cargo sets them.
+    fn authors(&self) -> &'static str;
+
+    /// Returns the license expression, or `None` if only a `license-file` is declared.
+    fn license(&self) -> Option<&'static str>;
+
+    /// Returns the target triple the package was built for, if captured.
+    fn target(&self) -> Option<&'static str>;
+
+    /// Returns the build time in seconds since the Unix epoch, if captured.
+    fn build_time_utc(&self) -> Option<u64>;
+}
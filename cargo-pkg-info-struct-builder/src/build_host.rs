@@ -0,0 +1,73 @@
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::process::Command;
+
+/// Environment variables read to determine the build host and user.
+pub const HOST_ENV_VARS: [&str; 5] = ["HOSTNAME", "COMPUTERNAME", "USER", "USERNAME", "LOGNAME"];
+
+/// How the build machine's hostname and user name are embedded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HostInfoPolicy {
+    /// Do not embed the hostname or user name.
+    #[default]
+    Omit,
+
+    /// Embed the values as-is. Only suitable for internal builds.
+    Raw,
+
+    /// Embed the SHA-256 of the salt and value, so artifacts from the same
+    /// machine can be matched without revealing its identity.
+    Hashed { salt: String },
+}
+
+impl HostInfoPolicy {
+    /// Applies the policy to a captured value, returning what to embed.
+    pub fn apply(&self, value: &str) -> Option<String> {
+        match self {
+            HostInfoPolicy::Omit => None,
+            HostInfoPolicy::Raw => Some(value.to_string()),
+            HostInfoPolicy::Hashed { salt } => {
+                // Separate the salt so `("ab", "c")` and `("a", "bc")` differ
+                let digest = Sha256::digest(format!("{}\0{}", salt, value));
+                Some(digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+            }
+        }
+    }
+}
+
+/// Returns the hostname of the build machine.
+///
+/// Reads `HOSTNAME` or `COMPUTERNAME`, then falls back to the `hostname`
+/// command and `/etc/hostname`.
+pub fn hostname() -> Option<String> {
+    let from_env = || {
+        ["HOSTNAME", "COMPUTERNAME"]
+            .into_iter()
+            .find_map(|var| env::var(var).ok())
+    };
+    let from_command = || {
+        let output = Command::new("hostname").output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8(output.stdout).ok())?
+    };
+    let from_file = || fs::read_to_string("/etc/hostname").ok();
+
+    from_env()
+        .or_else(from_command)
+        .or_else(from_file)
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+}
+
+/// Returns the name of the user running the build, from `USER`, `USERNAME`
+/// or `LOGNAME`.
+pub fn username() -> Option<String> {
+    ["USER", "USERNAME", "LOGNAME"]
+        .into_iter()
+        .find_map(|var| env::var(var).ok())
+        .map(|username| username.trim().to_string())
+        .filter(|username| !username.is_empty())
+}
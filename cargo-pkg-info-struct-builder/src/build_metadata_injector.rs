@@ -0,0 +1,1006 @@
+use crate::build_host::{hostname, username, HostInfoPolicy, HOST_ENV_VARS};
+use crate::build_time::{
+    format_date, format_rfc3339, source_date_epoch, system_time_now_millis, BuildTimeSource,
+    TimestampMode, SOURCE_DATE_EPOCH,
+};
+use crate::ci::CiInfo;
+use crate::codegen::{
+    fn_ident, lines_const_name, lines_fn_ident, render_custom_values, render_custom_vars,
+    render_license, render_package_info_impl, render_template,
+};
+use crate::custom_value::CustomValue;
+use crate::env_snapshot::{is_glob, snapshot_env, ENTRY_SEPARATOR};
+use crate::error::InjectError;
+use crate::features::enabled_features;
+use crate::git_info::{GitInfo, GitRepo, VcsSource, CARGO_VCS_INFO_FILE};
+use crate::inject_build_metadata::get_cargo_field;
+use crate::inject_build_metadata::{
+    get_manifest_field, is_valid_env_var_name, read_cargo_manifest, set_cargo_env_var,
+};
+use crate::rust_version::{MsrvPolicy, RustVersion};
+use crate::rustflags::{effective_rustflags, redact_rustflags};
+use crate::toolchain::{probe_cargo_version, RustcInfo};
+use crate::visibility::Visibility;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use string_auto_indent::auto_indent;
+use toml::Value;
+
+/// Embedded template for the generated `CargoPkgInfo` struct.
+const TEMPLATE: &str = include_str!("inject_build_metadata.struct.rs");
+
+/// Target configuration captured alongside `BUILD_TARGET`.
+///
+/// List values (family, atomic widths, CPU features) are comma-separated, as
+/// Cargo sets them.
+const TARGET_CFG_VARS: [(&str, &str); 9] = [
+    ("CARGO_CFG_TARGET_ARCH", "BUILD_TARGET_ARCH"),
+    ("CARGO_CFG_TARGET_OS", "BUILD_TARGET_OS"),
+    ("CARGO_CFG_TARGET_ENV", "BUILD_TARGET_ENV"),
+    ("CARGO_CFG_TARGET_VENDOR", "BUILD_TARGET_VENDOR"),
+    ("CARGO_CFG_TARGET_FAMILY", "BUILD_TARGET_FAMILY"),
+    ("CARGO_CFG_TARGET_ENDIAN", "BUILD_TARGET_ENDIAN"),
+    (
+        "CARGO_CFG_TARGET_POINTER_WIDTH",
+        "BUILD_TARGET_POINTER_WIDTH",
+    ),
+    ("CARGO_CFG_TARGET_HAS_ATOMIC", "BUILD_TARGET_HAS_ATOMIC"),
+    ("CARGO_CFG_TARGET_FEATURE", "BUILD_TARGET_FEATURES"),
+];
+
+/// Build script settings captured by [`BuildMetadataInjector::with_profile`].
+const PROFILE_VARS: [(&str, &str); 5] = [
+    ("PROFILE", "BUILD_PROFILE"),
+    ("OPT_LEVEL", "BUILD_OPT_LEVEL"),
+    ("DEBUG", "BUILD_DEBUG"),
+    ("NUM_JOBS", "BUILD_NUM_JOBS"),
+    ("HOST", "BUILD_HOST_TRIPLE"),
+];
+
+/// Summary of a successful metadata injection.
+///
+/// Returned by [`BuildMetadataInjector::inject`] (and
+/// [`try_inject_build_metadata`](crate::try_inject_build_metadata)) so build
+/// scripts can inspect what was generated without parsing Cargo's build output.
+#[derive(Debug, Clone)]
+pub struct InjectReport {
+    /// The absolute path of the generated file.
+    pub dest_path: PathBuf,
+
+    /// `true` if the generated file was (re)written, `false` if it was
+    /// already up to date.
+    pub written: bool,
+
+    /// The absolute path of the embedded license file, if any.
+    pub license_path: Option<PathBuf>,
+
+    /// The copy of the license file written next to the generated file, which
+    /// the generated code embeds with `include_str!`.
+    pub license_dest_path: Option<PathBuf>,
+
+    /// The git metadata captured, if a repository, `.cargo_vcs_info.json`
+    /// file or override was available.
+    pub git: Option<GitInfo>,
+
+    /// The compiler details captured, if the `rustc` probe succeeded.
+    pub rustc: Option<RustcInfo>,
+
+    /// The CI build details captured, if the build ran on a recognized CI service.
+    pub ci: Option<CiInfo>,
+
+    /// The environment variables passed to the compiled crate, in the order
+    /// they were emitted. Values are stored before encoding.
+    pub env_vars: Vec<(String, String)>,
+
+    /// The warnings emitted through `cargo:warning`.
+    pub warnings: Vec<String>,
+}
+
+impl InjectReport {
+    /// Returns the value emitted for the given environment variable, if any.
+    pub fn env_var(&self, var_name: &str) -> Option<&str> {
+        self.env_vars
+            .iter()
+            .find(|(name, _)| name == var_name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets a Cargo environment variable and records it in the report.
+    fn emit(&mut self, var_name: &str, value: &str) {
+        set_cargo_env_var(var_name, value);
+        let entry = (var_name.to_string(), value.to_string());
+        self.env_vars.push(entry);
+    }
+
+    /// Re-emits each `(source, name)` pair whose source variable is set.
+    ///
+    /// Cargo only sets these for build scripts, so missing variables are skipped.
+    fn emit_from_env(&mut self, vars: &[(&str, &str)]) {
+        for (source, var_name) in vars {
+            if let Ok(value) = env::var(source) {
+                self.emit(var_name, &value);
+            }
+        }
+    }
+
+    /// Emits a `cargo:warning` and records it in the report.
+    fn warn(&mut self, message: String) {
+        println!("cargo:warning={}", message);
+        self.warnings.push(message);
+    }
+}
+
+/// Configures which metadata is captured and where the `CargoPkgInfo` struct
+/// is generated.
+///
+/// Most captures are enabled by default, matching
+/// [`inject_build_metadata`](crate::inject_build_metadata). Those that may
+/// expose private details, such as rustflags and the build host, are opt-in.
+///
+/// ```no_run
+/// use cargo_pkg_info_struct_builder::BuildMetadataInjector;
+/// use std::path::Path;
+///
+/// BuildMetadataInjector::new()
+///     .dest(Path::new("src").join("cargo_pkg_info.rs"))
+///     .with_timestamp(false)
+///     .with_license(true)
+///     .inject()
+///     .expect("Failed to inject build metadata");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BuildMetadataInjector {
+    dest_path: PathBuf,
+    visibility: Visibility,
+    with_package_info_trait: bool,
+    with_target: bool,
+    with_profile: bool,
+    with_features: bool,
+    with_rustflags: bool,
+    rustflags_deny_list: Vec<String>,
+    with_ci: bool,
+    build_host: HostInfoPolicy,
+    build_env_allow_list: Vec<String>,
+    custom_vars: Vec<(String, String)>,
+    custom_values: Vec<(String, CustomValue)>,
+    timestamp_mode: TimestampMode,
+    with_license: bool,
+    with_git: bool,
+    vcs_override: Option<GitInfo>,
+    with_describe_version: bool,
+    describe_tag_pattern: Option<String>,
+    with_toolchain: bool,
+    msrv_policy: MsrvPolicy,
+}
+
+impl Default for BuildMetadataInjector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildMetadataInjector {
+    /// Creates an injector writing to `src/cargo_pkg_info.rs` with the default
+    /// captures enabled.
+    pub fn new() -> Self {
+        Self {
+            dest_path: Path::new("src").join("cargo_pkg_info.rs"),
+            visibility: Visibility::Public,
+            with_package_info_trait: false,
+            with_target: true,
+            with_profile: true,
+            with_features: true,
+            with_rustflags: false,
+            rustflags_deny_list: Vec::new(),
+            with_ci: true,
+            build_host: HostInfoPolicy::Omit,
+            build_env_allow_list: Vec::new(),
+            custom_vars: Vec::new(),
+            custom_values: Vec::new(),
+            timestamp_mode: TimestampMode::Auto,
+            with_license: true,
+            with_git: true,
+            vcs_override: None,
+            with_describe_version: false,
+            describe_tag_pattern: None,
+            with_toolchain: true,
+            msrv_policy: MsrvPolicy::Ignore,
+        }
+    }
+
+    /// Sets the destination of the generated file, relative to the
+    /// consuming package's root directory.
+    pub fn dest(mut self, dest_path: impl Into<PathBuf>) -> Self {
+        self.dest_path = dest_path.into();
+        self
+    }
+
+    /// Sets the visibility of the generated struct, enums and `custom` module.
+    /// Defaults to [`Visibility::Public`].
+    ///
+    /// The generated file defines no exported macros, so it can be generated
+    /// several times (e.g. per binary) and included in separate modules.
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Enables or disables implementing the `PackageInfo` trait from
+    /// `cargo-pkg-info-runtime` for the generated struct, so that libraries
+    /// can accept any package's metadata. Disabled by default.
+    ///
+    /// The package must depend on `cargo-pkg-info-runtime`.
+    pub fn with_package_info_trait(mut self, enabled: bool) -> Self {
+        self.with_package_info_trait = enabled;
+        self
+    }
+
+    /// Enables or disables capturing the build target (`BUILD_TARGET`) and its
+    /// `cfg` values (`BUILD_TARGET_ARCH`, `BUILD_TARGET_OS`, etc.).
+    pub fn with_target(mut self, enabled: bool) -> Self {
+        self.with_target = enabled;
+        self
+    }
+
+    /// Enables or disables capturing the build profile, optimization level,
+    /// debug setting, job count and host triple (`BUILD_PROFILE`, etc.).
+    pub fn with_profile(mut self, enabled: bool) -> Self {
+        self.with_profile = enabled;
+        self
+    }
+
+    /// Enables or disables capturing the enabled Cargo features (`BUILD_FEATURES`).
+    pub fn with_features(mut self, enabled: bool) -> Self {
+        self.with_features = enabled;
+        self
+    }
+
+    /// Enables or disables capturing the effective rustflags (`BUILD_RUSTFLAGS`).
+    ///
+    /// Disabled by default, since flags can contain local paths or secrets.
+    /// Path-like and secret-looking flags are always redacted; see
+    /// [`redact_rustflags`](Self::redact_rustflags) to redact more.
+    pub fn with_rustflags(mut self, enabled: bool) -> Self {
+        self.with_rustflags = enabled;
+        self
+    }
+
+    /// Adds glob patterns (`*` and `?`, case-insensitive) for rustflags to
+    /// embed as `<redacted>`, in addition to
+    /// [`DEFAULT_RUSTFLAGS_DENY_LIST`](crate::rustflags::DEFAULT_RUSTFLAGS_DENY_LIST).
+    pub fn redact_rustflags<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rustflags_deny_list
+            .extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Enables or disables capturing CI build details (`BUILD_CI_PROVIDER`, etc.).
+    ///
+    /// Enabled by default. Nothing is captured for local builds, but on CI the
+    /// build number and URL change on every run, so disable this when builds
+    /// must be reproducible.
+    pub fn with_ci(mut self, enabled: bool) -> Self {
+        self.with_ci = enabled;
+        self
+    }
+
+    /// Sets how the build machine's hostname and user name are embedded
+    /// (`BUILD_HOST`, `BUILD_USER`). They are omitted by default.
+    pub fn with_build_host(mut self, policy: HostInfoPolicy) -> Self {
+        self.build_host = policy;
+        self
+    }
+
+    /// Adds environment variable names or glob patterns (`*` and `?`) whose
+    /// build-time values are forwarded to `CargoPkgInfo::build_env` (`BUILD_ENV`).
+    ///
+    /// Cargo reruns the build script when a listed variable changes. For glob
+    /// patterns only the variables matched at build time are watched.
+    pub fn with_build_env<I, S>(mut self, allow_list: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.build_env_allow_list
+            .extend(allow_list.into_iter().map(Into::into));
+        self
+    }
+
+    /// Registers a custom variable, set like
+    /// [`set_cargo_env_var`](crate::set_cargo_env_var) and exposed through a
+    /// generated accessor, e.g. `custom::deploy_env()` for `DEPLOY_ENV`.
+    ///
+    /// Multi-line values also get a `CargoPkgInfo::<NAME>_LINES` constant and
+    /// a `_lines` accessor. Registering the same name again replaces its value.
+    ///
+    /// Names starting with `CARGO_` or `BUILD_` are reserved for Cargo and the
+    /// captured metadata, and make [`inject`](Self::inject) fail.
+    pub fn set_custom_var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        self.custom_vars.retain(|(existing, _)| *existing != name);
+        self.custom_vars.push((name, value.into()));
+        self
+    }
+
+    /// Registers a multi-line custom variable, with its indentation normalized
+    /// like [`set_multi_line_cargo_env_var`](crate::set_multi_line_cargo_env_var).
+    ///
+    /// Blank lines at the start and end are removed, so a raw string literal
+    /// can begin and end on lines of its own.
+    pub fn set_multi_line_custom_var(self, name: impl Into<String>, value: &str) -> Self {
+        // Versions of `string-auto-indent` differ in whether they keep a
+        // leading blank line, so trim here rather than relying on either
+        let value = auto_indent(value);
+        self.set_custom_var(name, trim_blank_lines(&value))
+    }
+
+    /// Registers a typed value, generated as an associated constant such as
+    /// `CargoPkgInfo::MAX_CONNECTIONS`.
+    ///
+    /// ```no_run
+    /// use cargo_pkg_info_struct_builder::BuildMetadataInjector;
+    ///
+    /// BuildMetadataInjector::new()
+    ///     // pub const MAX_CONNECTIONS: u32 = 64;
+    ///     .set_custom_value("MAX_CONNECTIONS", 64u32)
+    ///     // pub const REGIONS: &'static [&'static str] = &["eu", "us"];
+    ///     .set_custom_value("REGIONS", vec!["eu", "us"])
+    ///     .inject()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// Registering the same name again replaces its value.
+    pub fn set_custom_value(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<CustomValue>,
+    ) -> Self {
+        let name = name.into();
+        self.custom_values.retain(|(existing, _)| *existing != name);
+        self.custom_values.push((name, value.into()));
+        self
+    }
+
+    /// Enables or disables capturing the build timestamp (`BUILD_TIME_UTC`).
+    ///
+    /// Enabling selects [`TimestampMode::Auto`]; disabling selects
+    /// [`TimestampMode::Omit`].
+    pub fn with_timestamp(mut self, enabled: bool) -> Self {
+        self.timestamp_mode = if enabled {
+            TimestampMode::Auto
+        } else {
+            TimestampMode::Omit
+        };
+        self
+    }
+
+    /// Sets how the build timestamp is determined.
+    ///
+    /// Unless omitted, `SOURCE_DATE_EPOCH` always takes precedence so that
+    /// reproducible builds produce identical output.
+    pub fn timestamp_mode(mut self, mode: TimestampMode) -> Self {
+        self.timestamp_mode = mode;
+        self
+    }
+
+    /// Enables or disables embedding the `license-file` contents.
+    ///
+    /// The license is copied next to the generated file (see
+    /// [`InjectReport::license_dest_path`]), which embeds it with `include_str!`.
+    /// When no license is embedded, a copy left by an earlier run is removed.
+    pub fn with_license(mut self, enabled: bool) -> Self {
+        self.with_license = enabled;
+        self
+    }
+
+    /// Enables or disables capturing git metadata (`BUILD_GIT_*`) from the
+    /// repository containing the package.
+    ///
+    /// When the package was built from a `.crate` archive, the commit recorded
+    /// in `.cargo_vcs_info.json` is used instead.
+    pub fn with_git(mut self, enabled: bool) -> Self {
+        self.with_git = enabled;
+        self
+    }
+
+    /// Embeds the given VCS metadata instead of detecting it, for builds where
+    /// neither a repository nor `.cargo_vcs_info.json` is available.
+    ///
+    /// The source is always reported as [`VcsSource::Override`]. The override
+    /// applies even if git detection is disabled.
+    pub fn with_vcs_override(mut self, git: GitInfo) -> Self {
+        self.vcs_override = Some(GitInfo {
+            source: VcsSource::Override,
+            ..git
+        });
+        self
+    }
+
+    /// Enables or disables deriving a display version (`BUILD_DESCRIBE_VERSION`)
+    /// from `git describe`, such as `1.2.3-beta.14+g1a2b3c4.dirty`.
+    ///
+    /// Disabled by default. A warning is emitted if the version cannot be
+    /// derived, or if the tag version differs from the `Cargo.toml` version.
+    pub fn with_describe_version(mut self, enabled: bool) -> Self {
+        self.with_describe_version = enabled;
+        self
+    }
+
+    /// Restricts the tags considered by `git describe` to those matching a
+    /// glob pattern, such as `v*`.
+    pub fn describe_tag_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.describe_tag_pattern = Some(pattern.into());
+        self
+    }
+
+    /// Enables or disables capturing the `rustc` and Cargo versions
+    /// (`BUILD_RUSTC_*`, `BUILD_LLVM_VERSION`, `BUILD_CARGO_VERSION`).
+    ///
+    /// If a probe fails, a warning is emitted and its values are left unset.
+    pub fn with_toolchain(mut self, enabled: bool) -> Self {
+        self.with_toolchain = enabled;
+        self
+    }
+
+    /// Sets what happens when the active `rustc` is older than the package's
+    /// `rust-version`. The check is disabled by default.
+    pub fn msrv_policy(mut self, policy: MsrvPolicy) -> Self {
+        self.msrv_policy = policy;
+        self
+    }
+
+    /// Captures the configured metadata and writes the generated file.
+    ///
+    /// If the directory for the file does not exist, it is created. The file is
+    /// only rewritten when its contents change. The same goes for the license
+    /// copy, which is removed if the license is not embedded. Cargo is
+    /// instructed to rerun the build script if `Cargo.toml` or the license file
+    /// changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InjectError`] if:
+    /// - The `CARGO_MANIFEST_DIR` environment variable is not set.
+    /// - `Cargo.toml` cannot be read or parsed.
+    /// - The license file is declared but cannot be read.
+    /// - The destination path does not name a file.
+    /// - The destination directory cannot be created or the file cannot be written.
+    /// - A stale license copy cannot be removed.
+    /// - The MSRV policy is [`MsrvPolicy::Deny`] and `rustc` is too old.
+    /// - `SOURCE_DATE_EPOCH` is not a non-negative integer, or is too large to
+    ///   be a timestamp in milliseconds.
+    /// - A custom variable name is invalid, starts with `CARGO_` or `BUILD_`,
+    ///   or generates the same accessor as another custom variable.
+    /// - A custom value name is invalid or clashes with a `<NAME>_LINES`
+    ///   constant, an integer does not fit its type, or a list or map is
+    ///   empty or mixes types.
+    pub fn inject(&self) -> Result<InjectReport, InjectError> {
+        // Retrieve the manifest directory
+        let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .ok_or_else(|| InjectError::MissingEnv {
+                var: "CARGO_MANIFEST_DIR".to_string(),
+            })?;
+
+        let dest_path = manifest_dir.join(&self.dest_path);
+        let destination_dir = match dest_path.parent() {
+            Some(dir) if dest_path.file_name().is_some() && !dest_path.is_dir() => dir,
+            _ => return Err(InjectError::InvalidDestination { path: dest_path }),
+        };
+
+        let manifest = read_cargo_manifest(&manifest_dir)?;
+
+        // Validate up front, since `set_cargo_env_var` panics on invalid names.
+        // `BUILD_` is reserved so custom variables cannot replace captured ones.
+        if let Some((name, _)) = self.custom_vars.iter().find(|(name, _)| {
+            !is_valid_env_var_name(name) || name.starts_with("CARGO_") || name.starts_with("BUILD_")
+        }) {
+            return Err(InjectError::InvalidCustomVar { name: name.clone() });
+        }
+        if let Some((name, _)) = self
+            .custom_values
+            .iter()
+            .find(|(name, _)| !is_valid_env_var_name(name))
+        {
+            return Err(InjectError::InvalidCustomVar { name: name.clone() });
+        }
+        let mut accessors = HashSet::new();
+        for (name, value) in &self.custom_vars {
+            // e.g. `FOO_LINES` and the lines of a multi-line `FOO` share `foo_lines`
+            let lines_fn = value.contains('\n').then(|| lines_fn_ident(name));
+            for ident in std::iter::once(fn_ident(name)).chain(lines_fn) {
+                if !accessors.insert(ident) {
+                    return Err(InjectError::InvalidCustomVar { name: name.clone() });
+                }
+            }
+        }
+        for (var_name, _) in self
+            .custom_vars
+            .iter()
+            .filter(|(_, value)| value.contains('\n'))
+        {
+            let const_name = lines_const_name(var_name);
+            if self
+                .custom_values
+                .iter()
+                .any(|(name, _)| *name == const_name)
+            {
+                return Err(InjectError::InvalidCustomValue {
+                    name: const_name,
+                    message: format!("conflicts with the lines of custom variable `{}`", var_name),
+                });
+            }
+        }
+
+        let mut report = InjectReport {
+            dest_path: dest_path.clone(),
+            written: false,
+            license_path: None,
+            license_dest_path: None,
+            git: None,
+            rustc: None,
+            ci: None,
+            env_vars: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        // Ensure the generated directory exists
+        fs::create_dir_all(destination_dir).map_err(|source| InjectError::Io {
+            path: destination_dir.to_path_buf(),
+            source,
+        })?;
+
+        if self.with_target {
+            // Retrieve the build target
+            let build_target = env::var("TARGET").unwrap_or_else(|_| "unknown-target".to_string());
+            report.emit("BUILD_TARGET", &build_target);
+            report.emit_from_env(&TARGET_CFG_VARS);
+        }
+
+        if self.with_profile {
+            report.emit_from_env(&PROFILE_VARS);
+        }
+
+        if self.with_features {
+            // Feature names cannot contain commas
+            report.emit("BUILD_FEATURES", &enabled_features(&manifest).join(","));
+        }
+
+        if self.with_rustflags {
+            // Flags may contain commas, so keep Cargo's own separator
+            let rustflags = redact_rustflags(&effective_rustflags(), &self.rustflags_deny_list);
+            report.emit("BUILD_RUSTFLAGS", &rustflags.join("\x1f"));
+        }
+
+        if self.with_toolchain {
+            inject_toolchain(&mut report);
+        }
+
+        if self.msrv_policy != MsrvPolicy::Ignore {
+            self.check_msrv(&mut report, &manifest_dir, &manifest)?;
+        }
+
+        if self.with_license {
+            // Copy the license next to the generated file, so that it can be
+            // embedded with `include_str!` rather than passed through the environment
+            if let Some(license_path) = get_manifest_field(&manifest, "license-file")
+                .map(|rel_path| manifest_dir.join(rel_path))
+            {
+                // `include_str!` requires UTF-8, so check it here
+                let license_content = fs::read_to_string(&license_path).map_err(|source| {
+                    InjectError::LicenseRead {
+                        path: license_path.clone(),
+                        source,
+                    }
+                })?;
+                let license_dest_path = license_dest_path(&dest_path);
+                write_if_changed(&license_dest_path, license_content.as_bytes())?;
+                report.license_path = Some(license_path);
+                report.license_dest_path = Some(license_dest_path);
+            }
+        }
+        if report.license_dest_path.is_none() {
+            // Otherwise a copy from an earlier run would be left behind
+            let stale_path = license_dest_path(&dest_path);
+            match fs::remove_file(&stale_path) {
+                Err(source) if source.kind() != io::ErrorKind::NotFound => {
+                    return Err(InjectError::Io {
+                        path: stale_path,
+                        source,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let mut rerun_paths = Vec::new();
+
+        let git = match &self.vcs_override {
+            Some(git) => Some(git.clone()),
+            None if self.with_git => detect_git_info(&manifest_dir, &mut rerun_paths),
+            None => None,
+        };
+        if let Some(git) = git {
+            emit_git_info(&mut report, &git);
+            report.git = Some(git);
+        }
+
+        if self.with_describe_version {
+            self.inject_describe_version(&mut report, &manifest_dir, &mut rerun_paths);
+        }
+
+        let mut rerun_env_vars: Vec<String> = Vec::new();
+
+        if self.timestamp_mode != TimestampMode::Omit {
+            self.inject_build_time(&mut report, &manifest_dir)?;
+            rerun_env_vars.push(SOURCE_DATE_EPOCH.to_string());
+        }
+
+        if self.with_ci {
+            if let Some(ci) = CiInfo::detect() {
+                emit_ci_info(&mut report, &ci);
+                report.ci = Some(ci);
+            }
+            rerun_env_vars.extend(CiInfo::env_vars().into_iter().map(String::from));
+        }
+
+        if self.build_host != HostInfoPolicy::Omit {
+            for (var_name, value) in [("BUILD_HOST", hostname()), ("BUILD_USER", username())] {
+                match value.and_then(|value| self.build_host.apply(&value)) {
+                    Some(value) => report.emit(var_name, &value),
+                    None => report.warn(format!("Cannot determine {}", var_name)),
+                }
+            }
+            rerun_env_vars.extend(HOST_ENV_VARS.map(String::from));
+        }
+
+        if !self.build_env_allow_list.is_empty() {
+            let mut entries = Vec::new();
+            for (name, value) in snapshot_env(&self.build_env_allow_list) {
+                if value.contains(ENTRY_SEPARATOR) {
+                    report.warn(format!(
+                        "Cannot forward environment variable `{}`: its value contains `\\x1f`",
+                        name
+                    ));
+                } else {
+                    entries.push(format!("{}={}", name, value));
+                }
+                rerun_env_vars.push(name);
+            }
+            rerun_env_vars.extend(
+                self.build_env_allow_list
+                    .iter()
+                    .filter(|pattern| !is_glob(pattern))
+                    .cloned(),
+            );
+            report.emit("BUILD_ENV", &entries.join(&ENTRY_SEPARATOR.to_string()));
+        }
+
+        for (name, value) in &self.custom_vars {
+            report.emit(name, value);
+        }
+
+        let license_file_name = report
+            .license_dest_path
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|file_name| file_name.to_string_lossy());
+        let package_info_impl = if self.with_package_info_trait {
+            render_package_info_impl()
+        } else {
+            String::new()
+        };
+        let contents = format!(
+            "{}{}{}{}{}",
+            render_template(TEMPLATE, self.visibility),
+            render_license(license_file_name.as_deref()),
+            package_info_impl,
+            render_custom_values(&self.custom_values)?,
+            render_custom_vars(&self.custom_vars, self.visibility)
+        );
+        report.written = write_if_changed(&dest_path, contents.as_bytes())?;
+
+        // Ensure Cargo rebuilds if Cargo.toml or the license file changes
+        println!("cargo:rerun-if-changed=Cargo.toml");
+        if let Some(license_path) = &report.license_path {
+            println!("cargo:rerun-if-changed={}", license_path.display());
+        }
+        for path in &rerun_paths {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+        rerun_env_vars.sort();
+        rerun_env_vars.dedup();
+        for var_name in &rerun_env_vars {
+            println!("cargo:rerun-if-env-changed={}", var_name);
+        }
+
+        Ok(report)
+    }
+
+    /// Compares the active `rustc` to the package's `rust-version`.
+    fn check_msrv(
+        &self,
+        report: &mut InjectReport,
+        manifest_dir: &Path,
+        manifest: &Value,
+    ) -> Result<(), InjectError> {
+        // Prefer Cargo's value, which resolves `rust-version.workspace = true`
+        let Some(required) = env::var("CARGO_PKG_RUST_VERSION")
+            .ok()
+            .filter(|version| !version.is_empty())
+            .or_else(|| get_manifest_field(manifest, "rust-version"))
+        else {
+            return Ok(());
+        };
+
+        let Some(required_version) = RustVersion::parse(&required) else {
+            report.warn(format!(
+                "Cannot check MSRV: invalid rust-version `{}`",
+                required
+            ));
+            return Ok(());
+        };
+
+        let rustc = match &report.rustc {
+            Some(rustc) => Ok(rustc.clone()),
+            None => RustcInfo::probe(),
+        };
+        let found = match rustc {
+            Ok(rustc) => rustc.version,
+            Err(err) => {
+                report.warn(format!("Cannot check MSRV: {}", err));
+                return Ok(());
+            }
+        };
+
+        if RustVersion::parse(&found).is_some_and(|found_version| found_version < required_version)
+        {
+            let err = InjectError::MsrvNotMet {
+                path: manifest_dir.join("Cargo.toml"),
+                required,
+                found,
+            };
+
+            match self.msrv_policy {
+                MsrvPolicy::Deny => return Err(err),
+                MsrvPolicy::Warn => report.warn(err.to_string()),
+                MsrvPolicy::Ignore => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Determines the build timestamp according to the timestamp mode.
+    fn inject_build_time(
+        &self,
+        report: &mut InjectReport,
+        manifest_dir: &Path,
+    ) -> Result<(), InjectError> {
+        let (build_time_utc_millis, source) = match source_date_epoch()? {
+            Some(epoch) => {
+                let millis = epoch
+                    .checked_mul(1000)
+                    .ok_or_else(|| InjectError::InvalidEnv {
+                        var: SOURCE_DATE_EPOCH.to_string(),
+                        value: epoch.to_string(),
+                    })?;
+                (millis, BuildTimeSource::SourceDateEpoch)
+            }
+            None if self.timestamp_mode == TimestampMode::GitCommit => {
+                let commit_time_utc = match &report.git {
+                    Some(git) => git.commit_time_utc,
+                    None => GitRepo::discover(manifest_dir)
+                        .and_then(|repo| GitInfo::from_repo(&repo).commit_time_utc),
+                };
+
+                // A commit time too large to be a timestamp is as good as none
+                match commit_time_utc.and_then(|secs| secs.checked_mul(1000)) {
+                    Some(millis) => (millis, BuildTimeSource::GitCommit),
+                    None => {
+                        report.warn(
+                            "Git commit time unavailable; using the current time as the build time"
+                                .to_string(),
+                        );
+                        (system_time_now_millis(), BuildTimeSource::SystemClock)
+                    }
+                }
+            }
+            None => (system_time_now_millis(), BuildTimeSource::SystemClock),
+        };
+        let build_time_utc = build_time_utc_millis / 1000;
+
+        // Formatted here so the generated code needs no date handling at runtime
+        report.emit("BUILD_TIME_UTC", &build_time_utc.to_string());
+        report.emit("BUILD_TIME_UTC_MILLIS", &build_time_utc_millis.to_string());
+        report.emit("BUILD_TIME_RFC3339", &format_rfc3339(build_time_utc));
+        report.emit("BUILD_DATE", &format_date(build_time_utc));
+        report.emit("BUILD_TIME_SOURCE", source.as_str());
+
+        Ok(())
+    }
+
+    /// Derives the display version from `git describe` and checks it against
+    /// the `Cargo.toml` version.
+    fn inject_describe_version(
+        &self,
+        report: &mut InjectReport,
+        manifest_dir: &Path,
+        rerun_paths: &mut Vec<PathBuf>,
+    ) {
+        let Some(repo) = GitRepo::discover(manifest_dir) else {
+            report.warn("Cannot derive describe version: no git repository found".to_string());
+            return;
+        };
+
+        // New tags change the nearest tag
+        rerun_paths.extend(repo.tags_dir());
+
+        let Some(describe) = repo.describe(self.describe_tag_pattern.as_deref()) else {
+            report.warn(format!(
+                "Cannot derive describe version: no tag matching `{}` found",
+                self.describe_tag_pattern.as_deref().unwrap_or("*")
+            ));
+            return;
+        };
+
+        let Some(version) = describe.version() else {
+            report.warn(format!(
+                "Cannot derive describe version: tag `{}` is not a MAJOR.MINOR.PATCH version",
+                describe.tag
+            ));
+            return;
+        };
+        report.emit("BUILD_DESCRIBE_VERSION", &version);
+
+        let pkg_version = env::var("CARGO_PKG_VERSION")
+            .ok()
+            .or_else(|| get_cargo_field(manifest_dir, "version"));
+        if let (Some(tag_version), Some(pkg_version)) = (describe.tag_version(), pkg_version) {
+            if tag_version != pkg_version {
+                report.warn(format!(
+                    "Git tag `{}` does not match the Cargo.toml version `{}`",
+                    describe.tag, pkg_version
+                ));
+            }
+        }
+    }
+}
+
+/// Probes `rustc` and Cargo, warning instead of failing if either is unavailable.
+fn inject_toolchain(report: &mut InjectReport) {
+    match RustcInfo::probe() {
+        Ok(rustc) => {
+            report.emit("BUILD_RUSTC_VERSION", &rustc.version);
+            report.emit("BUILD_RUSTC_CHANNEL", &rustc.channel);
+            if let Some(commit_hash) = &rustc.commit_hash {
+                report.emit("BUILD_RUSTC_COMMIT_HASH", commit_hash);
+            }
+            if let Some(host) = &rustc.host {
+                report.emit("BUILD_RUSTC_HOST", host);
+            }
+            if let Some(llvm_version) = &rustc.llvm_version {
+                report.emit("BUILD_LLVM_VERSION", llvm_version);
+            }
+            report.rustc = Some(rustc);
+        }
+        Err(err) => report.warn(format!("Cannot capture rustc version: {}", err)),
+    }
+
+    match probe_cargo_version() {
+        Ok(cargo_version) => report.emit("BUILD_CARGO_VERSION", &cargo_version),
+        Err(err) => report.warn(format!("Cannot capture Cargo version: {}", err)),
+    }
+}
+
+/// Emits the `BUILD_CI_*` variables for the values that are known.
+fn emit_ci_info(report: &mut InjectReport, ci: &CiInfo) {
+    report.emit("BUILD_CI_PROVIDER", ci.provider.as_str());
+    if let Some(build_id) = &ci.build_id {
+        report.emit("BUILD_CI_BUILD_ID", build_id);
+    }
+    if let Some(build_url) = &ci.build_url {
+        report.emit("BUILD_CI_BUILD_URL", build_url);
+    }
+    if let Some(job_name) = &ci.job_name {
+        report.emit("BUILD_CI_JOB_NAME", job_name);
+    }
+}
+
+/// Detects VCS metadata for the package, recording the files to watch.
+fn detect_git_info(manifest_dir: &Path, rerun_paths: &mut Vec<PathBuf>) -> Option<GitInfo> {
+    // A packaged crate's own VCS info takes precedence over any enclosing
+    // repository (e.g. a `vendor/` directory inside another project)
+    if let Some(git) = GitInfo::from_cargo_vcs_info(manifest_dir) {
+        rerun_paths.push(manifest_dir.join(CARGO_VCS_INFO_FILE));
+        return Some(git);
+    }
+
+    let repo = GitRepo::discover(manifest_dir)?;
+    let mut git = GitInfo::from_repo(&repo);
+    git.path_in_vcs = repo.path_in_vcs(manifest_dir);
+
+    // Keep the values fresh when switching branches or committing
+    rerun_paths.extend(repo.rerun_if_changed_paths());
+
+    Some(git)
+}
+
+/// Emits the `BUILD_GIT_*` variables for the values that are known.
+fn emit_git_info(report: &mut InjectReport, git: &GitInfo) {
+    report.emit("BUILD_VCS_SOURCE", git.source.as_str());
+    if let Some(commit_hash) = &git.commit_hash {
+        report.emit("BUILD_GIT_COMMIT_HASH", commit_hash);
+    }
+    if let Some(commit_short) = &git.commit_short {
+        report.emit("BUILD_GIT_COMMIT_SHORT", commit_short);
+    }
+    if let Some(branch) = &git.branch {
+        report.emit("BUILD_GIT_BRANCH", branch);
+    }
+    if let Some(tag) = &git.tag {
+        report.emit("BUILD_GIT_TAG", tag);
+    }
+    if let Some(dirty) = git.dirty {
+        report.emit("BUILD_GIT_DIRTY", &dirty.to_string());
+    }
+    if let Some(commit_time_utc) = git.commit_time_utc {
+        report.emit("BUILD_GIT_COMMIT_TIME_UTC", &commit_time_utc.to_string());
+    }
+    if let Some(path_in_vcs) = &git.path_in_vcs {
+        report.emit("BUILD_GIT_PATH_IN_VCS", path_in_vcs);
+    }
+}
+
+/// Strips the blank (empty or whitespace-only) lines at the start and end of
+/// `value`, keeping the lines in between as they are.
+fn trim_blank_lines(value: &str) -> &str {
+    let mut range: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for line in value.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if !content.trim().is_empty() {
+            let start = range.map_or(offset, |(start, _)| start);
+            range = Some((start, offset + content.len()));
+        }
+        offset += line.len();
+    }
+
+    range.map_or("", |(start, end)| &value[start..end])
+}
+
+/// Returns the path of the license copy for the generated file at
+/// `dest_path`: `cargo_pkg_info.rs` gets `cargo_pkg_info_license.txt`.
+fn license_dest_path(dest_path: &Path) -> PathBuf {
+    let stem = dest_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    dest_path.with_file_name(format!("{}_license.txt", stem))
+}
+
+/// Writes a file unless it already has the given contents, mapping failures
+/// to [`InjectError::Io`].
+///
+/// Returns `true` if the file was (re)written.
+fn write_if_changed(path: &Path, contents: &[u8]) -> Result<bool, InjectError> {
+    // Skipping identical writes avoids needless rebuilds
+    if fs::read(path).is_ok_and(|existing_contents| existing_contents == contents) {
+        println!("No changes to {}; skipping file write.", path.display());
+        return Ok(false);
+    }
+
+    fs::write(path, contents).map_err(|source| InjectError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    Ok(true)
+}
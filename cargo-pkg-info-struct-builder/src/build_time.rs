@@ -0,0 +1,130 @@
+use crate::error::InjectError;
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable used by reproducible builds to pin timestamps.
+///
+/// See <https://reproducible-builds.org/specs/source-date-epoch/>.
+pub const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+/// Number of seconds in a (leap-second-free) UTC day.
+const SECS_PER_DAY: u64 = 86_400;
+
+/// How the build timestamp (`BUILD_TIME_UTC`) is determined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimestampMode {
+    /// Use `SOURCE_DATE_EPOCH` if set, otherwise the current time.
+    #[default]
+    Auto,
+
+    /// Use `SOURCE_DATE_EPOCH` if set, otherwise the commit time of `HEAD`.
+    /// Falls back to the current time (with a warning) if neither is available.
+    GitCommit,
+
+    /// Do not embed a build timestamp.
+    Omit,
+}
+
+/// Where the embedded build timestamp came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildTimeSource {
+    /// The `SOURCE_DATE_EPOCH` environment variable.
+    SourceDateEpoch,
+
+    /// The commit time of `HEAD`.
+    GitCommit,
+
+    /// The system clock when the build script ran.
+    SystemClock,
+}
+
+impl BuildTimeSource {
+    /// Returns the identifier embedded in `BUILD_TIME_SOURCE`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildTimeSource::SourceDateEpoch => "source_date_epoch",
+            BuildTimeSource::GitCommit => "git_commit",
+            BuildTimeSource::SystemClock => "system_clock",
+        }
+    }
+}
+
+/// Parses a `SOURCE_DATE_EPOCH` value (seconds since the Unix epoch).
+///
+/// # Errors
+///
+/// Returns [`InjectError::InvalidEnv`] if the value is not a non-negative integer.
+pub fn parse_source_date_epoch(value: &str) -> Result<u64, InjectError> {
+    value
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| InjectError::InvalidEnv {
+            var: SOURCE_DATE_EPOCH.to_string(),
+            value: value.to_string(),
+        })
+}
+
+/// Reads `SOURCE_DATE_EPOCH`, returning `None` if it is unset or empty.
+pub(crate) fn source_date_epoch() -> Result<Option<u64>, InjectError> {
+    match env::var(SOURCE_DATE_EPOCH) {
+        Ok(value) if !value.trim().is_empty() => parse_source_date_epoch(&value).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Returns the current time in milliseconds since the Unix epoch.
+pub(crate) fn system_time_now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64
+}
+
+/// Formats seconds since the Unix epoch as an RFC 3339 UTC timestamp,
+/// e.g. `2023-11-14T22:13:20Z`.
+pub fn format_rfc3339(secs: u64) -> String {
+    let (year, month, day) = civil_from_days(secs / SECS_PER_DAY);
+    let secs_of_day = secs % SECS_PER_DAY;
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Formats seconds since the Unix epoch as a UTC calendar date, e.g. `2023-11-14`.
+pub fn format_date(secs: u64) -> String {
+    let (year, month, day) = civil_from_days(secs / SECS_PER_DAY);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Converts days since the Unix epoch to a proleptic Gregorian `(year, month, day)`.
+///
+/// Based on Howard Hinnant's `civil_from_days` algorithm, restricted to dates
+/// on or after 1970-01-01.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Shift the epoch to 0000-03-01 so leap days fall at the end of the year
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
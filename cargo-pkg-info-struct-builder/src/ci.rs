@@ -0,0 +1,218 @@
+use std::env;
+
+/// A continuous integration service, detected from its environment variables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    GitHubActions,
+    GitLabCi,
+    Buildkite,
+    CircleCi,
+    Jenkins,
+    TravisCi,
+    AzurePipelines,
+    BitbucketPipelines,
+    TeamCity,
+
+    /// An unrecognized service that sets `CI=true`.
+    Generic,
+}
+
+impl CiProvider {
+    /// Returns the identifier embedded in `BUILD_CI_PROVIDER`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CiProvider::GitHubActions => "github_actions",
+            CiProvider::GitLabCi => "gitlab_ci",
+            CiProvider::Buildkite => "buildkite",
+            CiProvider::CircleCi => "circleci",
+            CiProvider::Jenkins => "jenkins",
+            CiProvider::TravisCi => "travis_ci",
+            CiProvider::AzurePipelines => "azure_pipelines",
+            CiProvider::BitbucketPipelines => "bitbucket_pipelines",
+            CiProvider::TeamCity => "teamcity",
+            CiProvider::Generic => "generic",
+        }
+    }
+}
+
+/// The variables each provider is detected by and read from:
+/// `(provider, marker, build id, build URL, job name)`.
+///
+/// Providers are checked in order, so specific services come before generic
+/// variables such as `BUILD_NUMBER`.
+const PROVIDERS: [(CiProvider, &str, &str, &str, &str); 9] = [
+    (
+        CiProvider::GitHubActions,
+        "GITHUB_ACTIONS",
+        "GITHUB_RUN_ID",
+        "",
+        "GITHUB_JOB",
+    ),
+    (
+        CiProvider::GitLabCi,
+        "GITLAB_CI",
+        "CI_PIPELINE_ID",
+        "CI_PIPELINE_URL",
+        "CI_JOB_NAME",
+    ),
+    (
+        CiProvider::Buildkite,
+        "BUILDKITE",
+        "BUILDKITE_BUILD_NUMBER",
+        "BUILDKITE_BUILD_URL",
+        "BUILDKITE_LABEL",
+    ),
+    (
+        CiProvider::CircleCi,
+        "CIRCLECI",
+        "CIRCLE_BUILD_NUM",
+        "CIRCLE_BUILD_URL",
+        "CIRCLE_JOB",
+    ),
+    (
+        CiProvider::TravisCi,
+        "TRAVIS",
+        "TRAVIS_BUILD_NUMBER",
+        "TRAVIS_BUILD_WEB_URL",
+        "TRAVIS_JOB_NAME",
+    ),
+    (
+        CiProvider::AzurePipelines,
+        "TF_BUILD",
+        "BUILD_BUILDID",
+        "",
+        "SYSTEM_JOBDISPLAYNAME",
+    ),
+    (
+        CiProvider::BitbucketPipelines,
+        "BITBUCKET_BUILD_NUMBER",
+        "BITBUCKET_BUILD_NUMBER",
+        "",
+        "BITBUCKET_STEP_UUID",
+    ),
+    (
+        CiProvider::TeamCity,
+        "TEAMCITY_VERSION",
+        "BUILD_NUMBER",
+        "",
+        "TEAMCITY_BUILDCONF_NAME",
+    ),
+    (
+        CiProvider::Jenkins,
+        "JENKINS_URL",
+        "BUILD_NUMBER",
+        "BUILD_URL",
+        "JOB_NAME",
+    ),
+];
+
+/// Variables used to build URLs for providers that do not set one directly.
+const URL_VARS: [&str; 4] = [
+    "GITHUB_SERVER_URL",
+    "GITHUB_REPOSITORY",
+    "SYSTEM_COLLECTIONURI",
+    "SYSTEM_TEAMPROJECT",
+];
+
+/// CI build details captured for the generated `CargoPkgInfo` struct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiInfo {
+    pub provider: CiProvider,
+
+    /// The provider's run, pipeline or build number.
+    pub build_id: Option<String>,
+
+    /// A link to the build in the provider's web interface.
+    pub build_url: Option<String>,
+
+    /// The name of the job or step that ran the build.
+    pub job_name: Option<String>,
+}
+
+impl CiInfo {
+    /// Detects the CI provider from the current environment.
+    ///
+    /// Returns `None` for local builds.
+    pub fn detect() -> Option<CiInfo> {
+        CiInfo::detect_with(|name| env::var(name).ok())
+    }
+
+    /// Detects the CI provider using `var` to look up environment variables.
+    ///
+    /// Empty values are treated as unset.
+    pub fn detect_with(var: impl Fn(&str) -> Option<String>) -> Option<CiInfo> {
+        let var = |name: &str| var(name).filter(|value| !value.is_empty());
+
+        for (provider, marker, build_id, build_url, job_name) in PROVIDERS {
+            if var(marker).is_none() {
+                continue;
+            }
+
+            let build_id = var(build_id);
+            let build_url = match provider {
+                CiProvider::GitHubActions => {
+                    match (
+                        var("GITHUB_SERVER_URL"),
+                        var("GITHUB_REPOSITORY"),
+                        &build_id,
+                    ) {
+                        (Some(server), Some(repo), Some(id)) => {
+                            Some(format!("{}/{}/actions/runs/{}", server, repo, id))
+                        }
+                        _ => None,
+                    }
+                }
+                CiProvider::AzurePipelines => {
+                    match (
+                        var("SYSTEM_COLLECTIONURI"),
+                        var("SYSTEM_TEAMPROJECT"),
+                        &build_id,
+                    ) {
+                        (Some(collection), Some(project), Some(id)) => Some(format!(
+                            "{}/{}/_build/results?buildId={}",
+                            collection.trim_end_matches('/'),
+                            project,
+                            id
+                        )),
+                        _ => None,
+                    }
+                }
+                _ => var(build_url),
+            };
+
+            return Some(CiInfo {
+                provider,
+                build_id,
+                build_url,
+                job_name: var(job_name),
+            });
+        }
+
+        match var("CI").as_deref() {
+            Some("true" | "1") => Some(CiInfo {
+                provider: CiProvider::Generic,
+                build_id: var("BUILD_NUMBER"),
+                build_url: var("BUILD_URL"),
+                job_name: None,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns every variable that detection reads, for `rerun-if-env-changed`.
+    pub fn env_vars() -> Vec<&'static str> {
+        let mut vars: Vec<&'static str> = PROVIDERS
+            .iter()
+            .flat_map(|(_, marker, build_id, build_url, job_name)| {
+                [*marker, *build_id, *build_url, *job_name]
+            })
+            .chain(URL_VARS)
+            .chain(["CI", "BUILD_NUMBER", "BUILD_URL"])
+            .filter(|name| !name.is_empty())
+            .collect();
+
+        vars.sort_unstable();
+        vars.dedup();
+        vars
+    }
+}
@@ -0,0 +1,312 @@
+//! Rendering of the build-script-specific sections of the generated file.
+//!
+//! The static template only reads values through `option_env!`, so it is
+//! identical for every build. Sections that depend on the build script's
+//! configuration (such as custom variable accessors) are appended to it.
+
+use crate::custom_value::CustomValue;
+use crate::error::InjectError;
+use crate::visibility::Visibility;
+
+/// The line width used by rustfmt.
+const MAX_WIDTH: usize = 100;
+
+/// The widest item list rustfmt keeps on one line in an array.
+const ARRAY_WIDTH: usize = 60;
+
+/// The widest item rustfmt packs several to a line in a multi-line array.
+const SHORT_ITEM_WIDTH: usize = 10;
+
+/// Rust keywords that can be used as raw identifiers (`r#type`).
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that cannot be raw identifiers, so are suffixed with `_` instead.
+const RESERVED_KEYWORDS: &[&str] = &["crate", "self", "super"];
+
+/// Converts a variable name to a snake_case Rust identifier, escaping keywords.
+pub(crate) fn fn_ident(name: &str) -> String {
+    let ident = name.to_lowercase();
+
+    if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else if RESERVED_KEYWORDS.contains(&ident.as_str()) {
+        format!("{}_", ident)
+    } else {
+        ident
+    }
+}
+
+/// Formats a value as a Rust string literal.
+pub(crate) fn string_literal(value: &str) -> String {
+    // `Debug` escapes quotes, backslashes and control characters using
+    // Rust's own escape syntax
+    format!("{:?}", value)
+}
+
+/// Formats string literals as a `&[...]` array on one line, if rustfmt would
+/// keep it there.
+fn inline_str_array(items: &[String]) -> Option<String> {
+    let joined = items.join(", ");
+    (joined.chars().count() <= ARRAY_WIDTH).then(|| format!("&[{}]", joined))
+}
+
+/// Formats string literals as a `&[...]` array broken across lines, with the
+/// items indented one level deeper than `indent`.
+///
+/// Like rustfmt, short items are packed several to a line, while others get
+/// a line each.
+fn multi_line_str_array(items: &[String], indent: &str) -> String {
+    let item_indent = format!("{}    ", indent);
+    let mut lines: Vec<String> = Vec::new();
+
+    if items
+        .iter()
+        .all(|item| item.chars().count() <= SHORT_ITEM_WIDTH)
+    {
+        // Only a line holding every item may use the last column
+        let single_line = format!("{},", items.join(", "));
+        if item_indent.len() + single_line.chars().count() <= MAX_WIDTH {
+            lines.push(single_line);
+        } else {
+            let mut line = String::new();
+            for item in items {
+                let width = line.chars().count() + 1 + item.chars().count() + 1;
+                if !line.is_empty() && item_indent.len() + width >= MAX_WIDTH {
+                    lines.push(std::mem::take(&mut line));
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(item);
+                line.push(',');
+            }
+            lines.push(line);
+        }
+    } else {
+        lines.extend(items.iter().map(|item| format!("{},", item)));
+    }
+
+    let body: String = lines
+        .iter()
+        .map(|line| format!("{}{}\n", item_indent, line))
+        .collect();
+    format!("&[\n{}{}]", body, indent)
+}
+
+/// Applies `visibility` to the top-level items of the template, which are
+/// written as `pub`.
+pub(crate) fn render_template(template: &str, visibility: Visibility) -> String {
+    if visibility == Visibility::Public {
+        return template.to_string();
+    }
+
+    template
+        .split_inclusive('\n')
+        .map(|line| match line.strip_prefix("pub ") {
+            Some(rest) => format!("{} {}", visibility.as_str(), rest),
+            None => line.to_string(),
+        })
+        .collect()
+}
+
+/// Renders the `license_content` accessor, which embeds `license_file_name`
+/// (a file next to the generated one) with `include_str!`.
+pub(crate) fn render_license(license_file_name: Option<&str>) -> String {
+    let body = match license_file_name {
+        Some(file_name) => format!("Some(include_str!({}))", string_literal(file_name)),
+        None => "None".to_string(),
+    };
+
+    format!(
+        "\nimpl CargoPkgInfo {{\n    \
+         /// Returns the contents of the license file (embedded at build time).\n    \
+         #[allow(dead_code)]\n    \
+         pub const fn license_content() -> Option<&'static str> {{\n        \
+         {}\n    \
+         }}\n\
+         }}\n",
+        body
+    )
+}
+
+/// Returns the name of the lines constant generated for a multi-line custom
+/// variable, e.g. `RELEASE_NOTES_LINES`.
+pub(crate) fn lines_const_name(name: &str) -> String {
+    format!("{}_LINES", name)
+}
+
+/// Returns the name of the lines accessor generated for a multi-line custom
+/// variable, e.g. `release_notes_lines`.
+pub(crate) fn lines_fn_ident(name: &str) -> String {
+    format!("{}_lines", name.to_lowercase())
+}
+
+/// Renders an associated `&'static [&'static str]` constant, laid out the way
+/// rustfmt would so the generated file passes `cargo fmt --check`.
+fn render_str_slice_const(name: &str, items: &[String]) -> String {
+    let head = format!("    pub const {}: &'static [&'static str] =", name);
+
+    // Short arrays stay on one line, moved after the `=` if needed
+    if let Some(array) = inline_str_array(items) {
+        let width = array.chars().count() + 1;
+        if head.len() + 1 + width <= MAX_WIDTH {
+            return format!("{} {};\n", head, array);
+        }
+        if 8 + width <= MAX_WIDTH {
+            return format!("{}\n        {};\n", head, array);
+        }
+    }
+
+    format!("{} {};\n", head, multi_line_str_array(items, "    "))
+}
+
+/// Renders the implementation of `cargo_pkg_info_runtime::PackageInfo` for
+/// the generated struct.
+pub(crate) fn render_package_info_impl() -> String {
+    String::from(
+        "\nimpl ::cargo_pkg_info_runtime::PackageInfo for CargoPkgInfo {\n    \
+             fn name(&self) -> &'static str {\n        \
+                 env_or_empty(CargoPkgInfo::pkg_name())\n    \
+             }\n\n    \
+             fn version(&self) -> &'static str {\n        \
+                 env_or_empty(CargoPkgInfo::pkg_version())\n    \
+             }\n\n    \
+             fn authors(&self) -> &'static str {\n        \
+                 env_or_empty(CargoPkgInfo::authors())\n    \
+             }\n\n    \
+             fn license(&self) -> Option<&'static str> {\n        \
+                 CargoPkgInfo::license().filter(|license| !license.is_empty())\n    \
+             }\n\n    \
+             fn target(&self) -> Option<&'static str> {\n        \
+                 CargoPkgInfo::build_target()\n    \
+             }\n\n    \
+             fn build_time_utc(&self) -> Option<u64> {\n        \
+                 CargoPkgInfo::build_time_utc()\n    \
+             }\n\
+         }\n",
+    )
+}
+
+/// Renders the `custom` module with one accessor per custom variable, plus a
+/// `<NAME>_LINES` constant and a `_lines` accessor for multi-line values.
+///
+/// Returns an empty string if there are no custom variables.
+pub(crate) fn render_custom_vars(
+    custom_vars: &[(String, String)],
+    visibility: Visibility,
+) -> String {
+    if custom_vars.is_empty() {
+        return String::new();
+    }
+
+    let mut out = String::new();
+
+    let multi_line_vars: Vec<&(String, String)> = custom_vars
+        .iter()
+        .filter(|(_, value)| value.contains('\n'))
+        .collect();
+    if !multi_line_vars.is_empty() {
+        out.push_str(
+            "\n/// Lines of the multi-line custom variables registered in the build script.\n\
+             impl CargoPkgInfo {\n",
+        );
+
+        for (i, (name, value)) in multi_line_vars.into_iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+
+            let lines: Vec<String> = value.lines().map(string_literal).collect();
+            out.push_str("    #[allow(dead_code)]\n");
+            out.push_str(&render_str_slice_const(&lines_const_name(name), &lines));
+        }
+
+        out.push_str("}\n");
+    }
+
+    out.push_str(&format!(
+        "\n/// Accessors for the custom variables registered in the build script.\n\
+         #[allow(dead_code)]\n\
+         {} mod custom {{\n",
+        visibility.as_str()
+    ));
+
+    for (i, (name, value)) in custom_vars.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+
+        out.push_str(&format!(
+            "    /// Returns the value of `{name}`.\n    \
+             pub const fn {ident}() -> &'static str {{\n        \
+             {value}\n    \
+             }}\n",
+            name = name,
+            ident = fn_ident(name),
+            value = string_literal(value),
+        ));
+
+        if value.contains('\n') {
+            out.push_str(&format!(
+                "\n    /// Returns the lines of `{name}`.\n    \
+                 pub const fn {ident}() -> &'static [&'static str] {{\n        \
+                 super::CargoPkgInfo::{const_name}\n    \
+                 }}\n",
+                name = name,
+                ident = lines_fn_ident(name),
+                const_name = lines_const_name(name),
+            ));
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Renders an `impl CargoPkgInfo` block with one associated `const` per
+/// custom value.
+///
+/// Returns an empty string if there are no custom values.
+///
+/// # Errors
+///
+/// Returns [`InjectError::InvalidCustomValue`] if an integer does not fit its
+/// type, or if a list or map is empty or mixes types.
+pub(crate) fn render_custom_values(
+    custom_values: &[(String, CustomValue)],
+) -> Result<String, InjectError> {
+    if custom_values.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut out =
+        String::from("\n/// Typed values registered in the build script.\nimpl CargoPkgInfo {\n");
+
+    for (i, (name, value)) in custom_values.iter().enumerate() {
+        let rust_type = value
+            .rust_type()
+            .map_err(|message| InjectError::InvalidCustomValue {
+                name: name.clone(),
+                message,
+            })?;
+
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "    #[allow(dead_code)]\n    pub const {}: {} = {};\n",
+            name,
+            rust_type,
+            value.rust_literal()
+        ));
+    }
+
+    out.push_str("}\n");
+    Ok(out)
+}
@@ -0,0 +1,235 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// The Rust type of a [`CustomValue::Int`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    I8,
+    I16,
+    I32,
+    I64,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    Usize,
+}
+
+impl IntType {
+    /// Returns the Rust name of the type, e.g. `u32`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::Isize => "isize",
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::Usize => "usize",
+        }
+    }
+
+    /// Returns the smallest and largest values of the type.
+    ///
+    /// `isize` and `usize` use the pointer width of the target being built,
+    /// falling back to the host's when not run from a build script.
+    pub fn range(&self) -> (i128, i128) {
+        let pointer_bits = std::env::var("CARGO_CFG_TARGET_POINTER_WIDTH")
+            .ok()
+            .and_then(|bits| bits.parse::<u32>().ok())
+            .unwrap_or(usize::BITS);
+
+        match self {
+            IntType::I8 => (i8::MIN.into(), i8::MAX.into()),
+            IntType::I16 => (i16::MIN.into(), i16::MAX.into()),
+            IntType::I32 => (i32::MIN.into(), i32::MAX.into()),
+            IntType::I64 => (i64::MIN.into(), i64::MAX.into()),
+            IntType::Isize => (-(1 << (pointer_bits - 1)), (1 << (pointer_bits - 1)) - 1),
+            IntType::U8 => (0, u8::MAX.into()),
+            IntType::U16 => (0, u16::MAX.into()),
+            IntType::U32 => (0, u32::MAX.into()),
+            IntType::U64 => (0, u64::MAX.into()),
+            IntType::Usize => (0, (1 << pointer_bits) - 1),
+        }
+    }
+}
+
+/// A typed value embedded as an associated `const` of `CargoPkgInfo`.
+///
+/// Values are usually built with `From`, which keeps the Rust type of
+/// integers: `CustomValue::from(64u32)` generates a `u32` constant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomValue {
+    Int {
+        value: i128,
+        ty: IntType,
+    },
+    Bool(bool),
+    Float(f64),
+    Str(String),
+
+    /// Generated as a `&'static [T]`. All items must have the same type.
+    List(Vec<CustomValue>),
+
+    /// Generated as a `&'static [(&'static str, T)]`, sorted by key. All
+    /// values must have the same type.
+    Map(BTreeMap<String, CustomValue>),
+}
+
+impl CustomValue {
+    /// Returns the Rust type of the generated constant.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if an integer does not fit its
+    /// type, or if a list or map is empty or mixes types.
+    pub(crate) fn rust_type(&self) -> Result<String, String> {
+        match self {
+            CustomValue::Int { value, ty } => {
+                let (min, max) = ty.range();
+                if !(min..=max).contains(value) {
+                    return Err(format!("{} is out of range for `{}`", value, ty.as_str()));
+                }
+                Ok(ty.as_str().to_string())
+            }
+            CustomValue::Bool(_) => Ok("bool".to_string()),
+            CustomValue::Float(_) => Ok("f64".to_string()),
+            CustomValue::Str(_) => Ok("&'static str".to_string()),
+            CustomValue::List(items) => Ok(format!("&'static [{}]", common_type(items.iter())?)),
+            CustomValue::Map(entries) => Ok(format!(
+                "&'static [(&'static str, {})]",
+                common_type(entries.values())?
+            )),
+        }
+    }
+
+    /// Returns the value as a Rust constant expression.
+    pub(crate) fn rust_literal(&self) -> String {
+        match self {
+            // The constant's type annotation makes a suffix unnecessary
+            CustomValue::Int { value, .. } => value.to_string(),
+            CustomValue::Bool(value) => value.to_string(),
+            CustomValue::Float(value) if value.is_nan() => "f64::NAN".to_string(),
+            CustomValue::Float(value) if value.is_infinite() => {
+                let sign = if *value > 0.0 { "" } else { "NEG_" };
+                format!("f64::{}INFINITY", sign)
+            }
+            // `Debug` always includes a decimal point or exponent
+            CustomValue::Float(value) => format!("{:?}", value),
+            CustomValue::Str(value) => format!("{:?}", value),
+            CustomValue::List(items) => {
+                let items: Vec<String> = items.iter().map(CustomValue::rust_literal).collect();
+                format!("&[{}]", items.join(", "))
+            }
+            CustomValue::Map(entries) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| format!("({:?}, {})", key, value.rust_literal()))
+                    .collect();
+                format!("&[{}]", entries.join(", "))
+            }
+        }
+    }
+}
+
+/// Returns the type shared by every value.
+///
+/// Empty collections are rejected, since their item type cannot be inferred.
+fn common_type<'a>(mut values: impl Iterator<Item = &'a CustomValue>) -> Result<String, String> {
+    let Some(first) = values.next() else {
+        return Err("cannot infer the item type of an empty collection".to_string());
+    };
+
+    let ty = first.rust_type()?;
+    for value in values {
+        let other = value.rust_type()?;
+        if other != ty {
+            return Err(format!("mixed item types `{}` and `{}`", ty, other));
+        }
+    }
+
+    Ok(ty)
+}
+
+macro_rules! impl_from_int {
+    ($($int:ty => $ty:ident),* $(,)?) => {
+        $(
+            impl From<$int> for CustomValue {
+                fn from(value: $int) -> Self {
+                    CustomValue::Int {
+                        value: value as i128,
+                        ty: IntType::$ty,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    isize => Isize,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    usize => Usize,
+);
+
+impl From<bool> for CustomValue {
+    fn from(value: bool) -> Self {
+        CustomValue::Bool(value)
+    }
+}
+
+impl From<f64> for CustomValue {
+    fn from(value: f64) -> Self {
+        CustomValue::Float(value)
+    }
+}
+
+impl From<&str> for CustomValue {
+    fn from(value: &str) -> Self {
+        CustomValue::Str(value.to_string())
+    }
+}
+
+impl From<String> for CustomValue {
+    fn from(value: String) -> Self {
+        CustomValue::Str(value)
+    }
+}
+
+impl<T: Into<CustomValue>> From<Vec<T>> for CustomValue {
+    fn from(items: Vec<T>) -> Self {
+        CustomValue::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<K: Into<String>, V: Into<CustomValue>> From<BTreeMap<K, V>> for CustomValue {
+    fn from(entries: BTreeMap<K, V>) -> Self {
+        CustomValue::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<K: Into<String>, V: Into<CustomValue>> From<HashMap<K, V>> for CustomValue {
+    fn from(entries: HashMap<K, V>) -> Self {
+        CustomValue::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,97 @@
+/// The parsed output of `git describe --tags --long --dirty`.
+///
+/// For example, `v1.2.3-beta-14-g1a2b3c4-dirty` describes a commit 14 commits
+/// after the `v1.2.3-beta` tag, with uncommitted changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitDescribe {
+    /// The nearest tag reachable from `HEAD`.
+    pub tag: String,
+
+    /// The number of commits between the tag and `HEAD`.
+    pub distance: u64,
+
+    /// The abbreviated commit hash of `HEAD`.
+    pub commit_short: String,
+
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+}
+
+impl GitDescribe {
+    /// Parses the output of `git describe --tags --long --dirty`.
+    ///
+    /// Tags may themselves contain `-` (e.g. `v1.2.3-beta`), so the output is
+    /// parsed from the right.
+    pub fn parse(output: &str) -> Option<GitDescribe> {
+        let output = output.trim();
+        let (output, dirty) = match output.strip_suffix("-dirty") {
+            Some(rest) => (rest, true),
+            None => (output, false),
+        };
+
+        let (rest, commit) = output.rsplit_once('-')?;
+        let commit_short = commit.strip_prefix('g')?;
+        let (tag, distance) = rest.rsplit_once('-')?;
+
+        if tag.is_empty() || commit_short.is_empty() {
+            return None;
+        }
+
+        Some(GitDescribe {
+            tag: tag.to_string(),
+            distance: distance.parse().ok()?,
+            commit_short: commit_short.to_string(),
+            dirty,
+        })
+    }
+
+    /// Returns the version encoded in the tag, without any leading prefix
+    /// (such as `v` or `release-`) or build metadata.
+    ///
+    /// Returns `None` if the tag does not contain a version number.
+    pub fn tag_version(&self) -> Option<&str> {
+        let start = self.tag.find(|c: char| c.is_ascii_digit())?;
+        let version = &self.tag[start..];
+
+        Some(version.split('+').next().unwrap_or(version))
+    }
+
+    /// Derives a SemVer display version from the tag, distance and dirty flag.
+    ///
+    /// - On a clean, tagged commit this is the tag version (`1.2.3-beta`).
+    /// - After a pre-release tag, the distance extends the pre-release
+    ///   (`1.2.3-beta.14+g1a2b3c4`).
+    /// - After a release tag, the patch version is bumped so the result sorts
+    ///   after the release (`1.2.4-dev.14+g1a2b3c4`).
+    /// - Uncommitted changes add `.dirty` to the build metadata.
+    ///
+    /// Returns `None` if the tag version is not a `MAJOR.MINOR.PATCH` version.
+    pub fn version(&self) -> Option<String> {
+        let tag_version = self.tag_version()?;
+        let (core, pre) = match tag_version.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (tag_version, None),
+        };
+
+        let mut parts = core.split('.').map(|part| part.parse::<u64>());
+        let (major, minor, patch) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => (major, minor, patch),
+            _ => return None,
+        };
+
+        let mut version = match (self.distance, pre) {
+            (0, _) => tag_version.to_string(),
+            (distance, Some(pre)) => format!("{}-{}.{}", core, pre, distance),
+            (distance, None) => format!("{}.{}.{}-dev.{}", major, minor, patch + 1, distance),
+        };
+
+        if self.distance > 0 || self.dirty {
+            version.push_str(&format!("+g{}", self.commit_short));
+        }
+        if self.dirty {
+            version.push_str(".dirty");
+        }
+
+        Some(version)
+    }
+}
@@ -0,0 +1,51 @@
+//! Reversible encoding for values passed through `cargo:rustc-env`.
+//!
+//! Cargo reads build script output line by line, so values cannot contain
+//! line breaks. Line feeds and carriage returns are written as `\n` and `\r`,
+//! and backslashes are doubled so that existing `\n` sequences (e.g. in
+//! Windows paths or regular expressions) survive the round trip. Every other
+//! character, including `=` and non-ASCII text, is passed through unchanged.
+
+/// Encodes a value for `cargo:rustc-env`.
+pub fn encode_env_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            c => encoded.push(c),
+        }
+    }
+
+    encoded
+}
+
+/// Decodes a value written by [`encode_env_value`].
+///
+/// Unrecognized escape sequences are kept as-is.
+pub fn decode_env_value(encoded: &str) -> String {
+    let mut decoded = String::with_capacity(encoded.len());
+    let mut chars = encoded.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            decoded.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\\') => decoded.push('\\'),
+            Some('n') => decoded.push('\n'),
+            Some('r') => decoded.push('\r'),
+            Some(other) => {
+                decoded.push('\\');
+                decoded.push(other);
+            }
+            None => decoded.push('\\'),
+        }
+    }
+
+    decoded
+}
@@ -0,0 +1,25 @@
+use crate::glob::glob_match;
+use std::env;
+
+/// Separates `NAME=value` entries in the embedded `BUILD_ENV` list.
+pub const ENTRY_SEPARATOR: char = '\x1f';
+
+/// Returns the environment variables whose names match an entry in
+/// `allow_list`, sorted by name.
+///
+/// Entries are exact names or glob patterns (`*` and `?`). Variables whose
+/// names or values are not valid Unicode are skipped.
+pub fn snapshot_env(allow_list: &[String]) -> Vec<(String, String)> {
+    let mut vars: Vec<(String, String)> = env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+        .filter(|(name, _)| allow_list.iter().any(|pattern| glob_match(pattern, name)))
+        .collect();
+
+    vars.sort();
+    vars
+}
+
+/// Returns `true` if an allow-list entry is a glob pattern rather than a name.
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
@@ -0,0 +1,109 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors that can occur while injecting build metadata.
+///
+/// Every variant carries the environment variable or path involved so that
+/// build script failures point directly at the offending input.
+#[derive(Debug)]
+pub enum InjectError {
+    /// A required environment variable (e.g. `CARGO_MANIFEST_DIR`) is not set.
+    MissingEnv { var: String },
+
+    /// An environment variable is set to a value that cannot be used.
+    InvalidEnv { var: String, value: String },
+
+    /// `Cargo.toml` exists but could not be parsed.
+    ManifestParse { path: PathBuf, message: String },
+
+    /// The file referenced by `license-file` in `Cargo.toml` could not be read.
+    LicenseRead { path: PathBuf, source: io::Error },
+
+    /// The destination path does not point to a file inside a directory.
+    InvalidDestination { path: PathBuf },
+
+    /// The active `rustc` is older than the `rust-version` declared in `Cargo.toml`.
+    MsrvNotMet {
+        path: PathBuf,
+        required: String,
+        found: String,
+    },
+
+    /// A custom variable name is not a valid environment variable name,
+    /// starts with the reserved `CARGO_` or `BUILD_` prefix, or generates the
+    /// same accessor as another custom variable.
+    InvalidCustomVar { name: String },
+
+    /// A custom value cannot be represented as a Rust constant.
+    InvalidCustomValue { name: String, message: String },
+
+    /// Any other I/O failure (reading the manifest, creating directories,
+    /// writing the generated file).
+    Io { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for InjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InjectError::MissingEnv { var } => {
+                write!(f, "environment variable `{}` is not set", var)
+            }
+            InjectError::InvalidEnv { var, value } => {
+                write!(
+                    f,
+                    "environment variable `{}` has invalid value `{}`",
+                    var, value
+                )
+            }
+            InjectError::ManifestParse { path, message } => {
+                write!(f, "failed to parse {}: {}", path.display(), message)
+            }
+            InjectError::LicenseRead { path, source } => {
+                write!(
+                    f,
+                    "failed to read license file {}: {}",
+                    path.display(),
+                    source
+                )
+            }
+            InjectError::InvalidDestination { path } => {
+                write!(f, "invalid destination path: {}", path.display())
+            }
+            InjectError::MsrvNotMet {
+                path,
+                required,
+                found,
+            } => {
+                write!(
+                    f,
+                    "rustc {} is older than the rust-version {} required by {}",
+                    found,
+                    required,
+                    path.display()
+                )
+            }
+            InjectError::InvalidCustomVar { name } => {
+                write!(f, "invalid custom variable name `{}`", name)
+            }
+            InjectError::InvalidCustomValue { name, message } => {
+                write!(f, "invalid custom value `{}`: {}", name, message)
+            }
+            InjectError::Io { path, source } => {
+                write!(f, "I/O error at {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl Error for InjectError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InjectError::LicenseRead { source, .. } | InjectError::Io { source, .. } => {
+                Some(source)
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::env;
+use toml::Value;
+
+/// Prefix of the variables Cargo sets for each enabled feature.
+const CARGO_FEATURE_PREFIX: &str = "CARGO_FEATURE_";
+
+/// Returns the features enabled for the current build, sorted by name.
+///
+/// Cargo only exposes features to build scripts as upper-cased
+/// `CARGO_FEATURE_<NAME>` variables, so names are mapped back to their
+/// `Cargo.toml` spelling where possible (see [`resolve_feature_names`]).
+pub fn enabled_features(manifest: &Value) -> Vec<String> {
+    let env_names: Vec<String> = env::vars()
+        .filter_map(|(var, _)| Some(var.strip_prefix(CARGO_FEATURE_PREFIX)?.to_string()))
+        .collect();
+
+    resolve_feature_names(&env_names, &declared_features(manifest))
+}
+
+/// Returns the features declared in a manifest: the keys of `[features]`
+/// plus any optional dependencies, which Cargo treats as implicit features.
+pub fn declared_features(manifest: &Value) -> Vec<String> {
+    let mut features: Vec<String> = manifest
+        .get("features")
+        .and_then(Value::as_table)
+        .map(|table| table.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let optional_deps = |deps: Option<&Value>| -> Vec<String> {
+        deps.and_then(Value::as_table)
+            .map(|table| {
+                table
+                    .iter()
+                    .filter(|(_, dep)| dep.get("optional").and_then(Value::as_bool) == Some(true))
+                    .map(|(name, _)| name.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    features.extend(optional_deps(manifest.get("dependencies")));
+    if let Some(targets) = manifest.get("target").and_then(Value::as_table) {
+        for target in targets.values() {
+            features.extend(optional_deps(target.get("dependencies")));
+        }
+    }
+
+    features.sort();
+    features.dedup();
+    features
+}
+
+/// Maps the `<NAME>` part of `CARGO_FEATURE_<NAME>` variables back to feature
+/// names, sorted and deduplicated.
+///
+/// Cargo upper-cases feature names and replaces `-` with `_`, so each name is
+/// matched against the same transformation of the `declared` features.
+/// Names that match no declared feature are lower-cased.
+pub fn resolve_feature_names(env_names: &[String], declared: &[String]) -> Vec<String> {
+    let mut features: Vec<String> = env_names
+        .iter()
+        .map(|env_name| {
+            declared
+                .iter()
+                .find(|feature| feature.to_uppercase().replace('-', "_") == *env_name)
+                .cloned()
+                .unwrap_or_else(|| env_name.to_lowercase())
+        })
+        .collect();
+
+    features.sort();
+    features.dedup();
+    features
+}
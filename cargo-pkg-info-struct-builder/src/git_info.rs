@@ -0,0 +1,381 @@
+use crate::describe_version::GitDescribe;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// File written by `cargo package` with the VCS state of the packaged crate.
+pub const CARGO_VCS_INFO_FILE: &str = ".cargo_vcs_info.json";
+
+/// Maximum number of symbolic refs followed before giving up.
+const MAX_SYMREF_DEPTH: usize = 5;
+
+/// Length of the abbreviated commit hash.
+const SHORT_HASH_LEN: usize = 7;
+
+/// Locations of a git repository's working tree and metadata directories.
+///
+/// For a regular checkout, `git_dir` and `common_dir` are both the `.git`
+/// directory. For a linked worktree (`git worktree add`) or a submodule, `.git`
+/// is a file pointing to `git_dir`, and shared refs live in `common_dir`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitRepo {
+    /// The root of the working tree (the directory containing `.git`).
+    pub work_tree: PathBuf,
+
+    /// The per-worktree metadata directory (holds `HEAD`).
+    pub git_dir: PathBuf,
+
+    /// The shared metadata directory (holds `refs/` and `packed-refs`).
+    pub common_dir: PathBuf,
+}
+
+/// The resolved state of a repository's `HEAD`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitHead {
+    /// The full commit hash `HEAD` points to, if it could be resolved.
+    pub commit_hash: Option<String>,
+
+    /// The branch name, or `None` for a detached `HEAD`.
+    pub branch: Option<String>,
+
+    /// The full ref name `HEAD` points to (e.g. `refs/heads/main`).
+    pub ref_name: Option<String>,
+}
+
+impl GitRepo {
+    /// Finds the repository containing `start_dir` by walking up its ancestors.
+    ///
+    /// Returns `None` if no `.git` directory or file is found.
+    pub fn discover(start_dir: &Path) -> Option<GitRepo> {
+        start_dir.ancestors().find_map(|dir| {
+            let dot_git = dir.join(".git");
+
+            let git_dir = if dot_git.is_dir() {
+                dot_git
+            } else if dot_git.is_file() {
+                // Linked worktrees and submodules use a `gitdir: <path>` file
+                let contents = fs::read_to_string(&dot_git).ok()?;
+                let target = contents.trim().strip_prefix("gitdir:")?.trim();
+                dir.join(target)
+            } else {
+                return None;
+            };
+
+            let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+                Ok(contents) => git_dir.join(contents.trim()),
+                Err(_) => git_dir.clone(),
+            };
+
+            Some(GitRepo {
+                work_tree: dir.to_path_buf(),
+                git_dir,
+                common_dir,
+            })
+        })
+    }
+
+    /// Reads `HEAD` and resolves it to a commit hash.
+    ///
+    /// Handles symbolic refs stored as loose files or in `packed-refs`, as
+    /// well as detached `HEAD`s containing a raw hash.
+    pub fn head(&self) -> GitHead {
+        let Ok(contents) = fs::read_to_string(self.git_dir.join("HEAD")) else {
+            return GitHead::default();
+        };
+        let contents = contents.trim();
+
+        match contents.strip_prefix("ref:") {
+            Some(ref_name) => {
+                let ref_name = ref_name.trim().to_string();
+                GitHead {
+                    commit_hash: self.resolve_ref(&ref_name, 0),
+                    branch: ref_name.strip_prefix("refs/heads/").map(str::to_string),
+                    ref_name: Some(ref_name),
+                }
+            }
+            None => GitHead {
+                commit_hash: is_commit_hash(contents).then(|| contents.to_string()),
+                branch: None,
+                ref_name: None,
+            },
+        }
+    }
+
+    /// Returns the path of `dir` relative to the working tree, using `/`
+    /// separators (empty for the repository root).
+    pub fn path_in_vcs(&self, dir: &Path) -> Option<String> {
+        let relative = dir.strip_prefix(&self.work_tree).ok()?;
+        let components: Vec<_> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect();
+
+        Some(components.join("/"))
+    }
+
+    /// Returns the files whose changes affect `HEAD` or the dirty state, for
+    /// use with `cargo:rerun-if-changed`.
+    ///
+    /// A branch that only exists in `packed-refs` gets a loose ref on its next
+    /// commit, so the nearest existing directory that will hold it is watched
+    /// instead. If there is none, the missing ref itself is watched, which
+    /// makes Cargo rerun the build script every time.
+    pub fn rerun_if_changed_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![self.git_dir.join("HEAD")];
+
+        if let Some(ref_name) = self.head().ref_name {
+            let loose_ref = self.loose_ref_path(&ref_name);
+            let refs_root = if ref_name.starts_with("refs/") {
+                &self.common_dir
+            } else {
+                &self.git_dir
+            };
+
+            if loose_ref.is_file() {
+                paths.push(loose_ref);
+            } else {
+                let ref_dir = loose_ref
+                    .ancestors()
+                    .skip(1)
+                    .take_while(|dir| *dir != refs_root.as_path())
+                    .find(|dir| dir.is_dir())
+                    .map(Path::to_path_buf);
+                paths.push(ref_dir.unwrap_or(loose_ref));
+            }
+        }
+
+        // Staging changes is the closest cheap signal for the dirty state
+        for path in [
+            self.common_dir.join("packed-refs"),
+            self.git_dir.join("index"),
+        ] {
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+
+        paths
+    }
+
+    /// Runs `git describe` to find the nearest tag matching `pattern` (a glob
+    /// such as `v*`), or any tag if no pattern is given.
+    ///
+    /// Returns `None` if the `git` executable is unavailable or no tag matches.
+    pub fn describe(&self, pattern: Option<&str>) -> Option<GitDescribe> {
+        let abbrev = format!("--abbrev={}", SHORT_HASH_LEN);
+        let mut args = vec!["describe", "--tags", "--long", "--dirty", &abbrev];
+        if let Some(pattern) = pattern {
+            args.extend(["--match", pattern]);
+        }
+
+        GitDescribe::parse(&run_git(self, &args)?)
+    }
+
+    /// Returns the directory holding loose tags, for use with
+    /// `cargo:rerun-if-changed` when tags affect the build.
+    pub fn tags_dir(&self) -> Option<PathBuf> {
+        Some(self.common_dir.join("refs").join("tags")).filter(|path| path.is_dir())
+    }
+
+    /// Resolves a ref name to a commit hash, following symbolic refs.
+    fn resolve_ref(&self, ref_name: &str, depth: usize) -> Option<String> {
+        if depth > MAX_SYMREF_DEPTH {
+            return None;
+        }
+
+        if let Ok(contents) = fs::read_to_string(self.loose_ref_path(ref_name)) {
+            let contents = contents.trim();
+            return match contents.strip_prefix("ref:") {
+                Some(target) => self.resolve_ref(target.trim(), depth + 1),
+                None => is_commit_hash(contents).then(|| contents.to_string()),
+            };
+        }
+
+        self.resolve_packed_ref(ref_name)
+    }
+
+    /// Looks up a ref in the `packed-refs` file.
+    fn resolve_packed_ref(&self, ref_name: &str) -> Option<String> {
+        let packed_refs = fs::read_to_string(self.common_dir.join("packed-refs")).ok()?;
+
+        packed_refs
+            .lines()
+            // Skip the header and peeled tag lines
+            .filter(|line| !line.starts_with('#') && !line.starts_with('^'))
+            .find_map(|line| {
+                let (hash, name) = line.split_once(' ')?;
+                (name.trim() == ref_name && is_commit_hash(hash)).then(|| hash.to_string())
+            })
+    }
+
+    /// Returns the path of a loose ref file.
+    ///
+    /// Per-worktree refs (such as `HEAD`) live in `git_dir`; everything under
+    /// `refs/` is shared through `common_dir`.
+    fn loose_ref_path(&self, ref_name: &str) -> PathBuf {
+        if ref_name.starts_with("refs/") {
+            self.common_dir.join(ref_name)
+        } else {
+            self.git_dir.join(ref_name)
+        }
+    }
+}
+
+/// Where the captured VCS metadata came from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VcsSource {
+    /// Read from a live git repository.
+    #[default]
+    Git,
+
+    /// Read from the `.cargo_vcs_info.json` file of a packaged crate.
+    CargoVcsInfo,
+
+    /// Supplied explicitly through
+    /// [`BuildMetadataInjector::with_vcs_override`](crate::BuildMetadataInjector::with_vcs_override).
+    Override,
+}
+
+impl VcsSource {
+    /// Returns the identifier embedded in `BUILD_VCS_SOURCE`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VcsSource::Git => "git",
+            VcsSource::CargoVcsInfo => "cargo_vcs_info",
+            VcsSource::Override => "override",
+        }
+    }
+}
+
+/// Git metadata captured for the generated `CargoPkgInfo` struct.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitInfo {
+    /// Where these values came from.
+    pub source: VcsSource,
+
+    /// The full commit hash of `HEAD`.
+    pub commit_hash: Option<String>,
+
+    /// The abbreviated commit hash of `HEAD`.
+    pub commit_short: Option<String>,
+
+    /// The current branch, or `None` for a detached `HEAD`.
+    pub branch: Option<String>,
+
+    /// The tag pointing exactly at `HEAD`, if any.
+    pub tag: Option<String>,
+
+    /// Whether tracked files have uncommitted changes.
+    pub dirty: Option<bool>,
+
+    /// The commit time of `HEAD`, in seconds since the Unix epoch.
+    pub commit_time_utc: Option<u64>,
+
+    /// The package's path relative to the repository root (empty for the root).
+    pub path_in_vcs: Option<String>,
+}
+
+impl GitInfo {
+    /// Collects git metadata for the given repository.
+    ///
+    /// The commit hash and branch are read directly from the repository files.
+    /// The tag, dirty state and commit time require the `git` executable and are
+    /// left as `None` if it is unavailable.
+    pub fn from_repo(repo: &GitRepo) -> GitInfo {
+        let head = repo.head();
+        let commit_short = head.commit_hash.as_deref().map(short_hash);
+
+        // Without a commit there is nothing for `git` to describe
+        if head.commit_hash.is_none() {
+            return GitInfo {
+                branch: head.branch,
+                ..GitInfo::default()
+            };
+        }
+
+        let tag = run_git(repo, &["describe", "--tags", "--exact-match", "HEAD"])
+            .filter(|tag| !tag.is_empty());
+
+        let dirty = run_git(repo, &["status", "--porcelain", "--untracked-files=no"])
+            .map(|status| !status.is_empty());
+
+        let commit_time_utc = run_git(repo, &["log", "-1", "--format=%ct", "HEAD"])
+            .and_then(|time| time.parse().ok());
+
+        GitInfo {
+            commit_hash: head.commit_hash,
+            commit_short,
+            branch: head.branch,
+            tag,
+            dirty,
+            commit_time_utc,
+            path_in_vcs: None,
+            source: VcsSource::Git,
+        }
+    }
+
+    /// Reads the `.cargo_vcs_info.json` file written by `cargo package`.
+    ///
+    /// This file is present when a crate is built from a `.crate` archive (for
+    /// example a registry or vendored build), where no `.git` directory exists.
+    /// Only the commit hash, dirty flag and path are recorded; the branch, tag
+    /// and commit time are always `None`.
+    ///
+    /// Returns `None` if the file is missing or does not contain a git commit.
+    pub fn from_cargo_vcs_info(package_dir: &Path) -> Option<GitInfo> {
+        let contents = fs::read_to_string(package_dir.join(CARGO_VCS_INFO_FILE)).ok()?;
+        let vcs_info: Value = serde_json::from_str(&contents).ok()?;
+
+        let git = vcs_info.get("git")?;
+        let commit_hash = git
+            .get("sha1")?
+            .as_str()
+            .filter(|sha1| is_commit_hash(sha1))?;
+
+        Some(GitInfo {
+            commit_short: Some(short_hash(commit_hash)),
+            commit_hash: Some(commit_hash.to_string()),
+            // `cargo package` only records the flag when the tree was dirty
+            dirty: Some(git.get("dirty").and_then(Value::as_bool).unwrap_or(false)),
+            path_in_vcs: vcs_info
+                .get("path_in_vcs")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            source: VcsSource::CargoVcsInfo,
+            ..GitInfo::default()
+        })
+    }
+}
+
+/// Returns the abbreviated form of a commit hash.
+fn short_hash(commit_hash: &str) -> String {
+    commit_hash[..SHORT_HASH_LEN].to_string()
+}
+
+/// Runs a `git` subcommand in the repository's working tree and returns its
+/// trimmed standard output, or `None` if the command failed.
+fn run_git(repo: &GitRepo, args: &[&str]) -> Option<String> {
+    // `git status` would otherwise refresh the index, which is watched for
+    // changes, so that every build would trigger the next one
+    let output = Command::new("git")
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .arg("-C")
+        .arg(&repo.work_tree)
+        .args(args)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|stdout| stdout.trim().to_string())
+}
+
+/// Returns `true` if `s` looks like a full SHA-1 or SHA-256 commit hash.
+fn is_commit_hash(s: &str) -> bool {
+    (s.len() == 40 || s.len() == 64) && s.chars().all(|c| c.is_ascii_hexdigit())
+}
@@ -1,35 +1,58 @@
-use std::env;
+use crate::build_metadata_injector::{BuildMetadataInjector, InjectReport};
+use crate::encoding::encode_env_value;
+use crate::error::InjectError;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
 use string_auto_indent::auto_indent;
 use toml::Value;
 
-/// Macro to escape newlines by normalizing `\r\n` to `\n`,
-/// then replacing `\n` with `\\n` for environment variable compatibility.
+/// Macro to escape a value for `cargo:rustc-env`.
 ///
-/// This ensures multi-line values stored in environment variables are
-/// correctly formatted when used in Cargo build scripts.
-///
-/// Note: For simplicity this is intentionally normalized to Unix-style
-/// line endings, though testing is performed cross-platform.
+/// This now delegates to [`encode_env_value`], which also escapes
+/// backslashes and keeps `\r\n` line endings.
+#[deprecated(note = "use `encoding::encode_env_value` instead")]
 #[macro_export]
 macro_rules! escape_newlines {
     ($s:expr) => {
-        $s.replace("\r\n", "\n").replace('\n', "\\n")
+        $crate::encoding::encode_env_value(&$s)
     };
 }
 
 /// Injects build metadata, including license content if available.
 ///
+/// This is the panicking counterpart of [`try_inject_build_metadata`], kept
+/// for compatibility with existing build scripts.
+///
+/// # Arguments
+///
+/// * `project_dest_path` - A `PathBuf` representing the relative path to the target file.
+///   This file is where the build metadata will be injected.
+///
+/// # Panics
+///
+/// This function will panic if [`try_inject_build_metadata`] returns an error.
+/// Before panicking, the error is also emitted as a `cargo:warning` so that it
+/// is visible in Cargo's build output.
+pub fn inject_build_metadata(project_dest_path: PathBuf) {
+    if let Err(err) = try_inject_build_metadata(project_dest_path) {
+        println!("cargo:warning=Failed to inject build metadata: {}", err);
+        panic!("Failed to inject build metadata: {}", err);
+    }
+}
+
+/// Injects build metadata, including license content if available.
+///
+/// This is shorthand for `BuildMetadataInjector::new().dest(project_dest_path).inject()`
+/// with every capture enabled; use [`BuildMetadataInjector`] to choose what gets embedded.
+///
 /// This function gathers metadata such as:
 /// - The build timestamp in UTC (`BUILD_TIME_UTC`)
 /// - The target architecture/OS (`BUILD_TARGET`)
-/// - The contents of the license file (`LICENSE_CONTENT`), if specified in `Cargo.toml`
+/// - The contents of the license file, if specified in `Cargo.toml`
 ///
 /// It writes the metadata into a file specified by `project_dest_path`. If the
 /// directory for the file does not exist, it is created. The function also ensures
-/// that if `Cargo.toml`, `inject.rs`, or the license file changes, the build will be rerun.
+/// that if `Cargo.toml` or the license file changes, the build will be rerun.
 ///
 /// # Arguments
 ///
@@ -38,73 +61,50 @@ macro_rules! escape_newlines {
 ///
 /// # Behavior
 /// - Reads the license file path from `Cargo.toml`, if available.
-/// - Reads the license file's content and sets it as an environment variable (`LICENSE_CONTENT`).
-/// - Ensures that Cargo rebuilds if `Cargo.toml`, `inject.rs`, or the license file changes.
+/// - Copies the license file next to the generated file, which embeds it with `include_str!`.
+/// - Ensures that Cargo rebuilds if `Cargo.toml` or the license file changes.
 ///
-/// # Panics
+/// # Errors
 ///
-/// This function will panic if:
+/// Returns an [`InjectError`] if:
 /// - The `CARGO_MANIFEST_DIR` environment variable is not set.
-/// - The destination directory cannot be created.
-/// - The file cannot be written.
-/// - The parent directory of the file cannot be determined.
-pub fn inject_build_metadata(project_dest_path: PathBuf) {
-    // Retrieve the manifest directory
-    let manifest_dir =
-        PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"));
-
-    let dest_path = manifest_dir.join(&project_dest_path);
-    let destination_dir = dest_path.parent().unwrap();
-
-    // Ensure the generated directory exists
-    fs::create_dir_all(destination_dir).expect("Failed to create generated directory");
-
-    // Retrieve the build target
-    let build_target = env::var("TARGET").unwrap_or_else(|_| "unknown-target".to_string());
-    set_cargo_env_var("BUILD_TARGET", &build_target);
-
-    // Get the build time in UTC
-    let build_time_utc = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("Time went backwards")
-        .as_secs()
-        .to_string();
-    set_cargo_env_var("BUILD_TIME_UTC", &build_time_utc);
-
-    // Read and set the license content if available
-    if let Some(license_path) = get_license_file_path(&manifest_dir) {
-        if let Ok(license_content) = fs::read_to_string(&license_path) {
-            set_cargo_env_var("LICENSE_CONTENT", &license_content);
-        }
-    }
+/// - `Cargo.toml` cannot be read or parsed.
+/// - The license file is declared but cannot be read.
+/// - The destination path does not name a file.
+/// - The destination directory cannot be created or the file cannot be written.
+pub fn try_inject_build_metadata(project_dest_path: PathBuf) -> Result<InjectReport, InjectError> {
+    BuildMetadataInjector::new()
+        .dest(project_dest_path)
+        .inject()
+}
 
-    // Embed inject.rs as bytes at compile time
-    const CONTENTS: &[u8] = include_bytes!("inject_build_metadata.struct.rs");
-
-    // Check if the file already exists with the same content
-    if let Ok(existing_contents) = fs::read(&dest_path) {
-        if existing_contents == CONTENTS {
-            // If the contents are identical, skip rewriting
-            println!(
-                "No changes to {}; skipping file write.",
-                dest_path.display()
-            );
-            // We do still print rerun-if-changed triggers, so keep going
-        } else {
-            // Write the embedded bytes to cargo_pkg_info.rs
-            fs::write(&dest_path, CONTENTS).expect("Failed to write metadata file");
-        }
-    } else {
-        // If we couldn't read it (likely doesn't exist), just create it
-        fs::write(&dest_path, CONTENTS).expect("Failed to write metadata file");
-    }
+/// Reads and parses the consuming package's `Cargo.toml`.
+///
+/// # Errors
+///
+/// Returns [`InjectError::Io`] if the file cannot be read, or
+/// [`InjectError::ManifestParse`] if it is not valid TOML.
+pub(crate) fn read_cargo_manifest(manifest_dir: &Path) -> Result<Value, InjectError> {
+    let cargo_toml_path = manifest_dir.join("Cargo.toml");
+    let cargo_toml_content =
+        fs::read_to_string(&cargo_toml_path).map_err(|source| InjectError::Io {
+            path: cargo_toml_path.clone(),
+            source,
+        })?;
+
+    toml::from_str(&cargo_toml_content).map_err(|err| InjectError::ManifestParse {
+        path: cargo_toml_path,
+        message: err.to_string(),
+    })
+}
 
-    // Ensure Cargo rebuilds if Cargo.toml or the license file changes
-    println!("cargo:rerun-if-changed=Cargo.toml");
-    println!("cargo:rerun-if-changed=inject.rs");
-    if let Some(license_path) = get_license_file_path(&manifest_dir) {
-        println!("cargo:rerun-if-changed={}", license_path.display());
-    }
+/// Extracts a string value from the `[package]` section of a parsed manifest.
+pub(crate) fn get_manifest_field(manifest: &Value, field: &str) -> Option<String> {
+    manifest
+        .get("package")?
+        .get(field)?
+        .as_str()
+        .map(|s| s.to_string())
 }
 
 /// Reads `Cargo.toml`, parses it, and extracts the value of a specified field.
@@ -122,15 +122,9 @@ pub fn inject_build_metadata(project_dest_path: PathBuf) {
 /// * `Some(String)` - The extracted value if found.
 /// * `None` - If the key does not exist.
 pub fn get_cargo_field(manifest_dir: &Path, field: &str) -> Option<String> {
-    let cargo_toml_path = manifest_dir.join("Cargo.toml");
-    let cargo_toml_content = fs::read_to_string(&cargo_toml_path).ok()?;
-    let cargo_toml: Value = toml::from_str(&cargo_toml_content).ok()?;
+    let cargo_toml = read_cargo_manifest(manifest_dir).ok()?;
 
-    cargo_toml
-        .get("package")?
-        .get(field)?
-        .as_str()
-        .map(|s| s.to_string())
+    get_manifest_field(&cargo_toml, field)
 }
 
 /// Reads `Cargo.toml`, extracts the `license-file` path, and returns it.
@@ -167,7 +161,8 @@ pub fn get_license_file_path(manifest_dir: &Path) -> Option<PathBuf> {
 ///
 /// # Arguments
 /// * `var_name` - The name of the environment variable (validated).
-/// * `value` - The value to assign, with escaped newlines.
+/// * `value` - The value to assign, encoded with [`encode_env_value`] so that
+///   line breaks and backslashes survive.
 ///
 /// # Panics
 /// This function will panic if:
@@ -192,8 +187,7 @@ pub fn set_cargo_env_var(var_name: &str, value: &str) {
         var_name
     );
 
-    let formatted_value = escape_newlines!(value); // Escape newlines
-    println!("cargo:rustc-env={}={}", var_name, formatted_value);
+    println!("cargo:rustc-env={}={}", var_name, encode_env_value(value));
 }
 
 /// Sets an environment variable for Cargo with auto-indented multi-line content.
@@ -222,7 +216,7 @@ pub fn set_multi_line_cargo_env_var(var_name: &str, value: &str) {
 ///
 /// # Returns
 /// `true` if the name is valid, `false` otherwise.
-fn is_valid_env_var_name(name: &str) -> bool {
+pub(crate) fn is_valid_env_var_name(name: &str) -> bool {
     let mut chars = name.chars();
 
     // First character must be a letter (A-Z or a-z)
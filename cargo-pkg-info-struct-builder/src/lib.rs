@@ -1,4 +1,33 @@
+pub mod build_host;
+pub mod build_metadata_injector;
+pub mod build_time;
+pub mod ci;
+mod codegen;
+pub mod custom_value;
+pub mod describe_version;
+pub mod encoding;
+pub mod env_snapshot;
+pub mod error;
+pub mod features;
+pub mod git_info;
+mod glob;
 pub mod inject_build_metadata;
+pub mod rust_version;
+pub mod rustflags;
+pub mod toolchain;
+pub mod visibility;
+pub use build_host::HostInfoPolicy;
+pub use build_metadata_injector::{BuildMetadataInjector, InjectReport};
+pub use build_time::{BuildTimeSource, TimestampMode};
+pub use ci::{CiInfo, CiProvider};
+pub use custom_value::{CustomValue, IntType};
+pub use describe_version::GitDescribe;
+pub use error::InjectError;
+pub use git_info::{GitInfo, GitRepo, VcsSource};
 pub use inject_build_metadata::{
     inject_build_metadata, set_cargo_env_var, set_multi_line_cargo_env_var,
+    try_inject_build_metadata,
 };
+pub use rust_version::{MsrvPolicy, RustVersion};
+pub use toolchain::RustcInfo;
+pub use visibility::Visibility;
@@ -0,0 +1,59 @@
+use std::fmt;
+
+/// A Rust release version, as used by `rust-version` in `Cargo.toml`.
+///
+/// Versions order numerically by `major`, then `minor`, then `patch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RustVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl RustVersion {
+    /// Parses a version such as `1.84`, `1.84.1` or `1.86.0-nightly`.
+    ///
+    /// A missing patch component is treated as `0`, and any pre-release
+    /// suffix is ignored, matching how Cargo compares `rust-version`.
+    pub fn parse(version: &str) -> Option<RustVersion> {
+        let core = version.trim().split('-').next()?;
+        let mut parts = core.split('.').map(|part| part.parse::<u32>().ok());
+
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let patch = match parts.next() {
+            Some(patch) => patch?,
+            None => 0,
+        };
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(RustVersion {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl fmt::Display for RustVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// What to do when the active `rustc` is older than the package's `rust-version`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MsrvPolicy {
+    /// Do not check the `rust-version`.
+    #[default]
+    Ignore,
+
+    /// Emit a build warning.
+    Warn,
+
+    /// Fail with [`InjectError::MsrvNotMet`](crate::InjectError::MsrvNotMet).
+    Deny,
+}
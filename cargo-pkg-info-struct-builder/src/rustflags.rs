@@ -0,0 +1,61 @@
+use crate::glob::glob_match;
+use std::env;
+
+/// Placeholder embedded in place of a redacted flag.
+pub const REDACTED: &str = "<redacted>";
+
+/// Glob patterns for flags that are always redacted: anything path-like or
+/// secret-looking. Matching is case-insensitive.
+pub const DEFAULT_RUSTFLAGS_DENY_LIST: &[&str] = &[
+    "*/*",
+    "*\\*",
+    "~*",
+    "*token*",
+    "*secret*",
+    "*password*",
+    "*key=*",
+];
+
+/// Returns the effective rustflags for the current build.
+///
+/// Reads `CARGO_ENCODED_RUSTFLAGS` (separated by `\x1f`), falling back to the
+/// whitespace-separated `RUSTFLAGS` outside of build scripts.
+pub fn effective_rustflags() -> Vec<String> {
+    match env::var("CARGO_ENCODED_RUSTFLAGS") {
+        Ok(encoded) => encoded
+            .split('\x1f')
+            .filter(|flag| !flag.is_empty())
+            .map(String::from)
+            .collect(),
+        Err(_) => env::var("RUSTFLAGS")
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(String::from)
+            .collect(),
+    }
+}
+
+/// Replaces every flag matching [`DEFAULT_RUSTFLAGS_DENY_LIST`] or one of the
+/// `deny_list` glob patterns with [`REDACTED`].
+///
+/// Flags and their values are separate items (e.g. `-L` and the path that
+/// follows it), so only the offending value is redacted.
+pub fn redact_rustflags(flags: &[String], deny_list: &[String]) -> Vec<String> {
+    flags
+        .iter()
+        .map(|flag| {
+            let lower = flag.to_lowercase();
+            let denied = DEFAULT_RUSTFLAGS_DENY_LIST
+                .iter()
+                .copied()
+                .chain(deny_list.iter().map(String::as_str))
+                .any(|pattern| glob_match(&pattern.to_lowercase(), &lower));
+
+            if denied {
+                REDACTED.to_string()
+            } else {
+                flag.clone()
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,104 @@
+use std::env;
+use std::process::Command;
+
+/// Compiler details parsed from `rustc -vV`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RustcInfo {
+    /// The release version, e.g. `1.84.0` or `1.86.0-nightly`.
+    pub version: String,
+
+    /// The commit the compiler was built from.
+    pub commit_hash: Option<String>,
+
+    /// The release channel: `stable`, `beta`, `nightly` or `dev`.
+    pub channel: String,
+
+    /// The host triple the compiler runs on.
+    pub host: Option<String>,
+
+    /// The version of the bundled LLVM.
+    pub llvm_version: Option<String>,
+}
+
+impl RustcInfo {
+    /// Runs `$RUSTC -vV` (falling back to `rustc`) and parses its output.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the failure if the compiler cannot be run or
+    /// its output cannot be parsed.
+    pub fn probe() -> Result<RustcInfo, String> {
+        let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        let output = run_tool(&rustc, "-vV")?;
+
+        RustcInfo::parse(&output).ok_or_else(|| format!("unrecognized output from `{} -vV`", rustc))
+    }
+
+    /// Parses the output of `rustc -vV`.
+    ///
+    /// Returns `None` if the `release` line is missing.
+    pub fn parse(verbose_version: &str) -> Option<RustcInfo> {
+        let field = |name: &str| {
+            verbose_version.lines().find_map(|line| {
+                let value = line.strip_prefix(name)?.strip_prefix(':')?.trim();
+                (!value.is_empty() && value != "unknown").then(|| value.to_string())
+            })
+        };
+
+        let version = field("release")?;
+        let channel = ["nightly", "beta", "dev"]
+            .into_iter()
+            .find(|channel| version.contains(&format!("-{}", channel)))
+            .unwrap_or("stable")
+            .to_string();
+
+        Some(RustcInfo {
+            commit_hash: field("commit-hash"),
+            host: field("host"),
+            llvm_version: field("LLVM version"),
+            channel,
+            version,
+        })
+    }
+}
+
+/// Runs `$CARGO -V` (falling back to `cargo`) and returns the version number.
+///
+/// # Errors
+///
+/// Returns a description of the failure if Cargo cannot be run or its output
+/// cannot be parsed.
+pub fn probe_cargo_version() -> Result<String, String> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = run_tool(&cargo, "-V")?;
+
+    parse_cargo_version(&output).ok_or_else(|| format!("unrecognized output from `{} -V`", cargo))
+}
+
+/// Parses the output of `cargo -V`, e.g. `cargo 1.84.0 (66221abde 2024-11-19)`.
+pub fn parse_cargo_version(version: &str) -> Option<String> {
+    let mut words = version.split_whitespace();
+
+    match (words.next(), words.next()) {
+        (Some("cargo"), Some(version)) => Some(version.to_string()),
+        _ => None,
+    }
+}
+
+/// Runs a toolchain executable with a single argument and returns its output.
+fn run_tool(program: &str, arg: &str) -> Result<String, String> {
+    let output = Command::new(program)
+        .arg(arg)
+        .output()
+        .map_err(|err| format!("failed to run `{} {}`: {}", program, arg, err))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`{} {}` exited with {}",
+            program, arg, output.status
+        ));
+    }
+
+    String::from_utf8(output.stdout)
+        .map_err(|_| format!("`{} {}` produced non-UTF-8 output", program, arg))
+}
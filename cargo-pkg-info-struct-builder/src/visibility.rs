@@ -0,0 +1,20 @@
+/// The visibility of the items in the generated file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Visibility {
+    /// `pub`, so the metadata can be re-exported from a library.
+    #[default]
+    Public,
+
+    /// `pub(crate)`, keeping the metadata out of the crate's public API.
+    Crate,
+}
+
+impl Visibility {
+    /// Returns the Rust visibility keyword, e.g. `pub(crate)`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Visibility::Public => "pub",
+            Visibility::Crate => "pub(crate)",
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, HostInfoPolicy};
+
+#[test]
+fn test_host_info_policy_apply() {
+    assert_eq!(HostInfoPolicy::default(), HostInfoPolicy::Omit);
+    assert_eq!(HostInfoPolicy::Omit.apply("build-01"), None);
+    assert_eq!(
+        HostInfoPolicy::Raw.apply("build-01").as_deref(),
+        Some("build-01")
+    );
+
+    let hashed = HostInfoPolicy::Hashed {
+        salt: "pepper".to_string(),
+    };
+    assert_eq!(
+        hashed.apply("build-01").as_deref(),
+        Some("f99bee6b8381fbac4f5f11f9a78707b4dc2c50008cfb422a94d166d05d5ef27b")
+    );
+
+    // Values spanning more than one SHA-256 block
+    assert_eq!(
+        hashed.apply(&"x".repeat(100)).as_deref(),
+        Some("ed76028daff3e9b50c937eb242d2a0e4ffc24b1fafdfa9f9027290549a5ce211")
+    );
+
+    let other_salt = HostInfoPolicy::Hashed {
+        salt: "salt".to_string(),
+    };
+    assert_ne!(other_salt.apply("build-01"), hashed.apply("build-01"));
+}
+
+#[test]
+fn test_build_host_omitted_by_default() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let report = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .inject()
+        .unwrap();
+
+    assert_eq!(report.env_var("BUILD_HOST"), None);
+    assert_eq!(report.env_var("BUILD_USER"), None);
+}
+
+#[test]
+fn test_build_host_hashed() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let report = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .with_build_host(HostInfoPolicy::Hashed {
+            salt: "pepper".to_string(),
+        })
+        .inject()
+        .unwrap();
+
+    for var_name in ["BUILD_HOST", "BUILD_USER"] {
+        if let Some(value) = report.env_var(var_name) {
+            assert_eq!(value.len(), 64, "{} should be a SHA-256 hash", var_name);
+            assert!(value.chars().all(|c| c.is_ascii_hexdigit()));
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use cargo_pkg_info_struct_builder::build_time::{
+    format_date, format_rfc3339, parse_source_date_epoch,
+};
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, InjectError, TimestampMode};
+use std::env;
+use std::sync::Mutex;
+
+/// Serializes the tests in this file that call `inject`, since some of them
+/// change `SOURCE_DATE_EPOCH`.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_parse_source_date_epoch() {
+    assert_eq!(
+        parse_source_date_epoch("1700000000").unwrap(),
+        1_700_000_000
+    );
+    assert_eq!(parse_source_date_epoch(" 0\n").unwrap(), 0);
+
+    match parse_source_date_epoch("yesterday") {
+        Err(InjectError::InvalidEnv { var, value }) => {
+            assert_eq!(var, "SOURCE_DATE_EPOCH");
+            assert_eq!(value, "yesterday");
+        }
+        other => panic!("Expected InvalidEnv, got {:?}", other),
+    }
+    assert!(parse_source_date_epoch("-1").is_err());
+}
+
+#[test]
+fn test_format_timestamps() {
+    assert_eq!(format_rfc3339(0), "1970-01-01T00:00:00Z");
+    assert_eq!(format_rfc3339(1_700_000_000), "2023-11-14T22:13:20Z");
+    assert_eq!(format_rfc3339(253_402_300_799), "9999-12-31T23:59:59Z");
+
+    // Leap years, including the 100/400-year rules
+    assert_eq!(format_date(951_782_400), "2000-02-29");
+    assert_eq!(format_date(4_107_542_399), "2100-02-28");
+    assert_eq!(format_date(4_107_542_400), "2100-03-01");
+}
+
+#[test]
+fn test_timestamp_modes() {
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let temp_dir = tempfile::tempdir().unwrap();
+    let injector = BuildMetadataInjector::new().dest(temp_dir.path().join("cargo_pkg_info.rs"));
+
+    // `SOURCE_DATE_EPOCH` may legitimately be set by the environment running the tests
+    let expected_source = |fallback: &'static str| {
+        if env::var("SOURCE_DATE_EPOCH").is_ok_and(|value| !value.trim().is_empty()) {
+            "source_date_epoch"
+        } else {
+            fallback
+        }
+    };
+
+    let report = injector.clone().inject().unwrap();
+    let build_time_utc: u64 = report.env_var("BUILD_TIME_UTC").unwrap().parse().unwrap();
+    let build_time_utc_millis: u64 = report
+        .env_var("BUILD_TIME_UTC_MILLIS")
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(build_time_utc_millis / 1000, build_time_utc);
+    assert_eq!(
+        report.env_var("BUILD_TIME_RFC3339"),
+        Some(format_rfc3339(build_time_utc).as_str())
+    );
+    assert_eq!(
+        report.env_var("BUILD_DATE"),
+        Some(format_date(build_time_utc).as_str())
+    );
+    assert_eq!(
+        report.env_var("BUILD_TIME_SOURCE"),
+        Some(expected_source("system_clock"))
+    );
+
+    // This crate is tested from within its git repository
+    let report = injector
+        .clone()
+        .timestamp_mode(TimestampMode::GitCommit)
+        .inject()
+        .unwrap();
+    if let Some(commit_time_utc) = report.git.as_ref().and_then(|git| git.commit_time_utc) {
+        assert_eq!(
+            report.env_var("BUILD_TIME_SOURCE"),
+            Some(expected_source("git_commit"))
+        );
+        if expected_source("git_commit") == "git_commit" {
+            assert_eq!(
+                report.env_var("BUILD_TIME_UTC"),
+                Some(commit_time_utc.to_string().as_str())
+            );
+        }
+    }
+
+    let report = injector
+        .timestamp_mode(TimestampMode::Omit)
+        .inject()
+        .unwrap();
+    assert_eq!(report.env_var("BUILD_TIME_UTC"), None);
+    assert_eq!(report.env_var("BUILD_TIME_RFC3339"), None);
+    assert_eq!(report.env_var("BUILD_TIME_SOURCE"), None);
+}
+
+#[test]
+fn test_source_date_epoch_sets_build_time() {
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    env::set_var("SOURCE_DATE_EPOCH", "1700000000");
+    let injector = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .with_toolchain(false);
+
+    // Takes precedence over both the system clock and the commit time
+    for mode in [TimestampMode::Auto, TimestampMode::GitCommit] {
+        let report = injector.clone().timestamp_mode(mode).inject().unwrap();
+        assert_eq!(report.env_var("BUILD_TIME_UTC"), Some("1700000000"));
+        assert_eq!(
+            report.env_var("BUILD_TIME_UTC_MILLIS"),
+            Some("1700000000000")
+        );
+        assert_eq!(
+            report.env_var("BUILD_TIME_RFC3339"),
+            Some("2023-11-14T22:13:20Z")
+        );
+        assert_eq!(
+            report.env_var("BUILD_TIME_SOURCE"),
+            Some("source_date_epoch")
+        );
+    }
+
+    // The build time is also embedded in milliseconds, which must fit in a `u64`
+    env::set_var("SOURCE_DATE_EPOCH", (u64::MAX / 1000 + 1).to_string());
+    match injector.inject() {
+        Err(InjectError::InvalidEnv { var, value }) => {
+            assert_eq!(var, "SOURCE_DATE_EPOCH");
+            assert_eq!(value, "18446744073709552");
+        }
+        other => panic!("Expected InvalidEnv, got {:?}", other),
+    }
+
+    env::remove_var("SOURCE_DATE_EPOCH");
+}
@@ -0,0 +1,122 @@
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, CiInfo, CiProvider};
+use std::collections::HashMap;
+use std::env;
+
+fn detect(vars: &[(&str, &str)]) -> Option<CiInfo> {
+    let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+    CiInfo::detect_with(|name| vars.get(name).map(|value| value.to_string()))
+}
+
+#[test]
+fn test_detect_github_actions() {
+    let ci = detect(&[
+        ("CI", "true"),
+        ("GITHUB_ACTIONS", "true"),
+        ("GITHUB_RUN_ID", "1234567890"),
+        ("GITHUB_SERVER_URL", "https://github.com"),
+        ("GITHUB_REPOSITORY", "octo/app"),
+        ("GITHUB_JOB", "test"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        ci,
+        CiInfo {
+            provider: CiProvider::GitHubActions,
+            build_id: Some("1234567890".to_string()),
+            build_url: Some("https://github.com/octo/app/actions/runs/1234567890".to_string()),
+            job_name: Some("test".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_detect_providers() {
+    let ci = detect(&[
+        ("GITLAB_CI", "true"),
+        ("CI_PIPELINE_ID", "42"),
+        ("CI_PIPELINE_URL", "https://gitlab.com/p/-/pipelines/42"),
+        ("CI_JOB_NAME", "build"),
+    ])
+    .unwrap();
+    assert_eq!(ci.provider, CiProvider::GitLabCi);
+    assert_eq!(ci.build_id.as_deref(), Some("42"));
+    assert_eq!(
+        ci.build_url.as_deref(),
+        Some("https://gitlab.com/p/-/pipelines/42")
+    );
+
+    let ci = detect(&[
+        ("BUILDKITE", "true"),
+        ("BUILDKITE_BUILD_NUMBER", "7"),
+        (
+            "BUILDKITE_BUILD_URL",
+            "https://buildkite.com/org/p/builds/7",
+        ),
+    ])
+    .unwrap();
+    assert_eq!(ci.provider, CiProvider::Buildkite);
+    assert_eq!(ci.job_name, None);
+
+    // `BUILD_NUMBER` alone is ambiguous, so Jenkins is detected by `JENKINS_URL`
+    let ci = detect(&[
+        ("JENKINS_URL", "https://ci.example.com/"),
+        ("BUILD_NUMBER", "99"),
+        ("JOB_NAME", "release"),
+    ])
+    .unwrap();
+    assert_eq!(ci.provider, CiProvider::Jenkins);
+    assert_eq!(ci.build_id.as_deref(), Some("99"));
+    assert_eq!(ci.job_name.as_deref(), Some("release"));
+}
+
+#[test]
+fn test_detect_generic_and_local() {
+    let ci = detect(&[("CI", "true"), ("BUILD_NUMBER", "5")]).unwrap();
+    assert_eq!(ci.provider, CiProvider::Generic);
+    assert_eq!(ci.build_id.as_deref(), Some("5"));
+
+    assert_eq!(detect(&[]), None);
+    assert_eq!(detect(&[("BUILD_NUMBER", "5")]), None);
+    assert_eq!(detect(&[("CI", "false")]), None);
+    assert_eq!(detect(&[("GITHUB_ACTIONS", "")]), None);
+}
+
+#[test]
+fn test_ci_env_vars() {
+    let env_vars = CiInfo::env_vars();
+
+    for name in [
+        "CI",
+        "GITHUB_RUN_ID",
+        "CI_PIPELINE_ID",
+        "BUILD_NUMBER",
+        "BUILDKITE_BUILD_URL",
+    ] {
+        assert!(env_vars.contains(&name), "{} should be watched", name);
+    }
+    assert!(!env_vars.contains(&""));
+}
+
+#[test]
+fn test_ci_info_enabled_by_default() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    env::set_var("GITHUB_ACTIONS", "true");
+    env::set_var("GITHUB_RUN_ID", "1234567890");
+    let injector = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .with_toolchain(false);
+
+    let report = injector.clone().inject().unwrap();
+    assert_eq!(report.env_var("BUILD_CI_PROVIDER"), Some("github_actions"));
+    assert_eq!(report.env_var("BUILD_CI_BUILD_ID"), Some("1234567890"));
+
+    // Run-specific values can be left out for reproducible builds
+    let report = injector.with_ci(false).inject().unwrap();
+    assert_eq!(report.env_var("BUILD_CI_PROVIDER"), None);
+    assert_eq!(report.env_var("BUILD_CI_BUILD_ID"), None);
+
+    env::remove_var("GITHUB_ACTIONS");
+    env::remove_var("GITHUB_RUN_ID");
+}
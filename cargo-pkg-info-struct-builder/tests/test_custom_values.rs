@@ -0,0 +1,116 @@
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, CustomValue, InjectError, IntType};
+use std::collections::BTreeMap;
+use std::fs;
+
+#[test]
+fn test_custom_values_generated() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dest_path = temp_dir.path().join("cargo_pkg_info.rs");
+
+    BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .set_custom_value("MAX_CONNECTIONS", 64u32)
+        .set_custom_value("OFFSET", -3i8)
+        .set_custom_value("ENABLED", false)
+        .set_custom_value("RATIO", 2.0)
+        .set_custom_value("LIMIT", f64::INFINITY)
+        .set_custom_value("NAME", "say \"hi\"")
+        .set_custom_value("MATRIX", vec![vec![1u8, 2], vec![3]])
+        .set_custom_value("PORTS", BTreeMap::from([("https", 443u16), ("http", 80)]))
+        .inject()
+        .unwrap();
+
+    let contents = fs::read_to_string(&dest_path).unwrap();
+    syn::parse_file(&contents).expect("Generated Rust file is invalid!");
+
+    for expected in [
+        "pub const MAX_CONNECTIONS: u32 = 64;",
+        "pub const OFFSET: i8 = -3;",
+        "pub const ENABLED: bool = false;",
+        "pub const RATIO: f64 = 2.0;",
+        "pub const LIMIT: f64 = f64::INFINITY;",
+        r#"pub const NAME: &'static str = "say \"hi\"";"#,
+        "pub const MATRIX: &'static [&'static [u8]] = &[&[1, 2], &[3]];",
+        r#"pub const PORTS: &'static [(&'static str, u16)] = &[("http", 80), ("https", 443)];"#,
+    ] {
+        assert!(contents.contains(expected), "Missing `{}`", expected);
+    }
+}
+
+#[test]
+fn test_custom_value_from() {
+    assert_eq!(
+        CustomValue::from(64u32),
+        CustomValue::Int {
+            value: 64,
+            ty: IntType::U32
+        }
+    );
+    assert_eq!(
+        CustomValue::from(vec!["a"]),
+        CustomValue::List(vec![CustomValue::Str("a".to_string())])
+    );
+}
+
+#[test]
+fn test_custom_value_mixed_types() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let result = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .set_custom_value(
+            "MIXED",
+            CustomValue::List(vec![CustomValue::from(1u8), CustomValue::from("two")]),
+        )
+        .inject();
+
+    match result {
+        Err(InjectError::InvalidCustomValue { name, message }) => {
+            assert_eq!(name, "MIXED");
+            assert!(message.contains("mixed item types"), "{}", message);
+        }
+        other => panic!("Expected InvalidCustomValue, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_custom_value_out_of_range() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let result = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .set_custom_value(
+            "LEVELS",
+            vec![CustomValue::Int {
+                value: 300,
+                ty: IntType::U8,
+            }],
+        )
+        .inject();
+
+    match result {
+        Err(InjectError::InvalidCustomValue { name, message }) => {
+            assert_eq!(name, "LEVELS");
+            assert_eq!(message, "300 is out of range for `u8`");
+        }
+        other => panic!("Expected InvalidCustomValue, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_custom_value_empty_collection() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let result = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .set_custom_value("EMPTY", Vec::<u8>::new())
+        .inject();
+
+    match result {
+        Err(InjectError::InvalidCustomValue { name, message }) => {
+            assert_eq!(name, "EMPTY");
+            assert!(message.contains("empty collection"), "{}", message);
+        }
+        other => panic!("Expected InvalidCustomValue, got {:?}", other),
+    }
+}
@@ -0,0 +1,166 @@
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, InjectError};
+use std::fs;
+
+#[test]
+fn test_custom_var_accessors_generated() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dest_path = temp_dir.path().join("cargo_pkg_info.rs");
+
+    let report = BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .set_custom_var("DEPLOY_ENV", "staging")
+        .set_custom_var("TYPE", "quoted \"value\" with \\ backslash")
+        .set_custom_var("SELF", "self")
+        .set_multi_line_custom_var("RELEASE_NOTES", "First line\nSecond line")
+        .set_custom_var("DEPLOY_ENV", "production")
+        .inject()
+        .unwrap();
+
+    assert_eq!(report.env_var("DEPLOY_ENV"), Some("production"));
+    assert_eq!(
+        report.env_var("RELEASE_NOTES"),
+        Some("First line\nSecond line")
+    );
+
+    let contents = fs::read_to_string(&dest_path).unwrap();
+    syn::parse_file(&contents).expect("Generated Rust file is invalid!");
+
+    assert!(contents.contains("pub mod custom {"));
+    assert!(
+        contents.contains("pub const fn deploy_env() -> &'static str {\n        \"production\"")
+    );
+    assert!(
+        !contents.contains("\"staging\""),
+        "Replaced values should not be generated"
+    );
+    assert!(contents.contains("pub const fn r#type()"));
+    assert!(contents.contains(r#""quoted \"value\" with \\ backslash""#));
+    assert!(contents.contains("pub const fn self_()"));
+    assert!(contents.contains("pub const fn release_notes_lines() -> &'static [&'static str]"));
+    assert!(contents.contains(
+        "pub const RELEASE_NOTES_LINES: &'static [&'static str] = &[\"First line\", \"Second line\"];"
+    ));
+    assert!(!contents.contains("deploy_env_lines"));
+    assert!(!contents.contains("DEPLOY_ENV_LINES"));
+}
+
+#[test]
+fn test_multi_line_custom_var_trims_blank_lines() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let report = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .set_multi_line_custom_var(
+            "RELEASE_NOTES",
+            r#"
+            First line
+
+                Indented line
+            "#,
+        )
+        .set_multi_line_custom_var("BLANK", "\n  \r\n\n")
+        .inject()
+        .unwrap();
+
+    // Inner blank lines and indentation are kept
+    assert_eq!(
+        report.env_var("RELEASE_NOTES"),
+        Some("First line\n\n    Indented line")
+    );
+    assert_eq!(report.env_var("BLANK"), Some(""));
+}
+
+#[test]
+fn test_custom_var_lines_conflict() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let result = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .set_multi_line_custom_var("RELEASE_NOTES", "First line\nSecond line")
+        .set_custom_value("RELEASE_NOTES_LINES", 2u32)
+        .inject();
+
+    match result {
+        Err(InjectError::InvalidCustomValue { name, .. }) => {
+            assert_eq!(name, "RELEASE_NOTES_LINES")
+        }
+        other => panic!("Expected InvalidCustomValue, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_custom_var_lines_layout() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dest_path = temp_dir.path().join("cargo_pkg_info.rs");
+
+    let digits: Vec<String> = (1..=25).map(|i| i.to_string()).collect();
+    BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .set_custom_var("SHORT_NOTES", "alpha\nbeta\ngamma\ndelta\nepsilon")
+        .set_custom_var("LONG_NOTES", "x".repeat(50) + "\n" + &"y".repeat(50))
+        .set_custom_var("DIGITS", digits.join("\n"))
+        .inject()
+        .unwrap();
+
+    let contents = fs::read_to_string(&dest_path).unwrap();
+
+    // The same layouts rustfmt picks for a short, long and many-item array
+    for expected in [
+        "    pub const SHORT_NOTES_LINES: &'static [&'static str] =\n        \
+         &[\"alpha\", \"beta\", \"gamma\", \"delta\", \"epsilon\"];\n",
+        &format!(
+            "    pub const LONG_NOTES_LINES: &'static [&'static str] = &[\n        \
+             \"{}\",\n        \"{}\",\n    ];\n",
+            "x".repeat(50),
+            "y".repeat(50)
+        ),
+        "    pub const DIGITS_LINES: &'static [&'static str] = &[\n        \
+         \"1\", \"2\", \"3\", \"4\", \"5\", \"6\", \"7\", \"8\", \"9\", \"10\", \"11\", \"12\", \
+         \"13\", \"14\", \"15\", \"16\",\n        \
+         \"17\", \"18\", \"19\", \"20\", \"21\", \"22\", \"23\", \"24\", \"25\",\n    ];\n",
+    ] {
+        assert!(contents.contains(expected), "Missing:\n{}", expected);
+    }
+}
+
+#[test]
+fn test_custom_var_accessor_conflict() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    // Both generate `custom::release_notes_lines`
+    let result = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .set_multi_line_custom_var("RELEASE_NOTES", "First line\nSecond line")
+        .set_custom_var("RELEASE_NOTES_LINES", "value")
+        .inject();
+
+    match result {
+        Err(InjectError::InvalidCustomVar { name }) => assert_eq!(name, "RELEASE_NOTES_LINES"),
+        other => panic!("Expected InvalidCustomVar, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_custom_var_invalid_name() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    // `BUILD_` names would replace the captured metadata
+    for name in [
+        "INVALID NAME",
+        "CARGO_RESERVED",
+        "1ST",
+        "BUILD_TARGET",
+        "BUILD_TIME_UTC",
+        "BUILD_ENV",
+    ] {
+        let result = BuildMetadataInjector::new()
+            .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+            .set_custom_var(name, "value")
+            .inject();
+
+        match result {
+            Err(InjectError::InvalidCustomVar { name: invalid }) => assert_eq!(invalid, name),
+            other => panic!("Expected InvalidCustomVar for `{}`, got {:?}", name, other),
+        }
+    }
+}
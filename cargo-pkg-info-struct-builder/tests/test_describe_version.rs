@@ -0,0 +1,60 @@
+use cargo_pkg_info_struct_builder::GitDescribe;
+
+#[test]
+fn test_parse_describe_output() {
+    let describe = GitDescribe::parse("v1.2.3-beta-14-g1a2b3c4-dirty\n").unwrap();
+    assert_eq!(
+        describe,
+        GitDescribe {
+            tag: "v1.2.3-beta".to_string(),
+            distance: 14,
+            commit_short: "1a2b3c4".to_string(),
+            dirty: true,
+        }
+    );
+    assert_eq!(describe.tag_version(), Some("1.2.3-beta"));
+
+    let describe = GitDescribe::parse("release-2.0.0-0-gabcdef0").unwrap();
+    assert_eq!(describe.tag, "release-2.0.0");
+    assert_eq!(describe.distance, 0);
+    assert!(!describe.dirty);
+    assert_eq!(describe.tag_version(), Some("2.0.0"));
+
+    // Output without `--long` is rejected
+    assert_eq!(GitDescribe::parse("v1.2.3"), None);
+    assert_eq!(GitDescribe::parse(""), None);
+}
+
+#[test]
+fn test_describe_version() {
+    let version = |output: &str| GitDescribe::parse(output).unwrap().version();
+
+    // Exactly on a tag
+    assert_eq!(version("v1.2.3-0-g1a2b3c4"), Some("1.2.3".to_string()));
+    assert_eq!(
+        version("v1.2.3-beta-0-g1a2b3c4"),
+        Some("1.2.3-beta".to_string())
+    );
+
+    // Commits after a pre-release tag extend the pre-release
+    assert_eq!(
+        version("v1.2.3-beta-14-g1a2b3c4-dirty"),
+        Some("1.2.3-beta.14+g1a2b3c4.dirty".to_string())
+    );
+
+    // Commits after a release tag bump the patch version
+    assert_eq!(
+        version("v1.2.3-14-g1a2b3c4"),
+        Some("1.2.4-dev.14+g1a2b3c4".to_string())
+    );
+
+    // Uncommitted changes on a tag
+    assert_eq!(
+        version("v1.2.3-0-g1a2b3c4-dirty"),
+        Some("1.2.3+g1a2b3c4.dirty".to_string())
+    );
+
+    // Tags without a full version cannot be converted
+    assert_eq!(version("nightly-3-g1a2b3c4"), None);
+    assert_eq!(version("v1.2-3-g1a2b3c4"), None);
+}
@@ -0,0 +1,161 @@
+use cargo_pkg_info_struct_builder::encoding::{decode_env_value, encode_env_value};
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, GitInfo};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Compiles `main_src` next to the generated file in `dir` the way Cargo
+/// would, with the encoded `env_vars` set, and returns the binary's path.
+fn compile_with_generated_file(
+    dir: &Path,
+    main_src: &str,
+    env_vars: &[(String, String)],
+) -> PathBuf {
+    fs::write(dir.join("main.rs"), main_src).unwrap();
+
+    let binary = dir.join("round_trip");
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let mut command = Command::new(rustc);
+    command
+        .args(["--edition", "2021", "--crate-name", "round_trip", "-o"])
+        .arg(&binary)
+        .arg(dir.join("main.rs"));
+    for (name, value) in env_vars {
+        command.env(name, encode_env_value(value));
+    }
+
+    let output = command.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    binary
+}
+
+#[test]
+fn test_encoding_round_trip() {
+    let values = [
+        "",
+        "plain",
+        "two\nlines",
+        "crlf\r\nline",
+        r"C:\new\raw",
+        r"a literal \n stays",
+        "trailing\\",
+        "key=value=more",
+        "café ✓ 🦀",
+    ];
+
+    for value in values {
+        let encoded = encode_env_value(value);
+        assert!(!encoded.contains(['\n', '\r']), "{:?}", encoded);
+        assert_eq!(decode_env_value(&encoded), value);
+    }
+}
+
+#[test]
+fn test_encoding_format() {
+    assert_eq!(encode_env_value("a\nb"), r"a\nb");
+    assert_eq!(encode_env_value(r"a\nb"), r"a\\nb");
+    assert_eq!(encode_env_value("a\r\nb"), r"a\r\nb");
+}
+
+#[test]
+fn test_decode_unknown_escapes() {
+    // Unknown sequences and a lone trailing backslash are kept as-is
+    assert_eq!(decode_env_value(r"a\tb"), r"a\tb");
+    assert_eq!(decode_env_value("end\\"), "end\\");
+}
+
+#[test]
+fn test_generated_accessors_decode_values() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dest_path = temp_dir.path().join("cargo_pkg_info.rs");
+
+    let report = BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .with_toolchain(false)
+        .with_vcs_override(GitInfo {
+            branch: Some(r"feature\new-parser".to_string()),
+            ..GitInfo::default()
+        })
+        .inject()
+        .unwrap();
+
+    let binary = compile_with_generated_file(
+        temp_dir.path(),
+        r#"
+mod cargo_pkg_info;
+use cargo_pkg_info::CargoPkgInfo;
+
+fn main() {
+    assert_eq!(CargoPkgInfo::git_branch(), Some(r"feature\new-parser"));
+}
+"#,
+        &report.env_vars,
+    );
+
+    let status = Command::new(binary).status().unwrap();
+    assert!(status.success(), "Decoded branch name did not match");
+}
+
+#[test]
+fn test_generated_decoder_matches_library() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let report = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .with_toolchain(false)
+        .inject()
+        .unwrap();
+    let binary = compile_with_generated_file(
+        temp_dir.path(),
+        r#"
+mod cargo_pkg_info;
+use cargo_pkg_info::CargoPkgInfo;
+
+fn main() {
+    for arg in std::env::args().skip(1) {
+        println!("{:?}", CargoPkgInfo::decode_env_value(&arg));
+    }
+}
+"#,
+        &report.env_vars,
+    );
+
+    // The generated file cannot depend on this crate, so it has its own copy
+    let inputs = [
+        "",
+        "plain",
+        r"two\nlines",
+        r"crlf\r\nline",
+        r"C:\\new\\raw",
+        r"a\\nb",
+        r"a\tb",
+        r"\q\",
+        "end\\",
+        "\\",
+        "café ✓ 🦀 \\é",
+        "key=value=more",
+    ];
+    let output = Command::new(binary).args(inputs).output().unwrap();
+    assert!(output.status.success());
+
+    let expected: String = inputs
+        .iter()
+        .map(|input| format!("{:?}\n", decode_env_value(input)))
+        .collect();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_escape_newlines() {
+    use cargo_pkg_info_struct_builder::escape_newlines;
+
+    assert_eq!(escape_newlines!("a\r\nb"), encode_env_value("a\r\nb"));
+    assert_eq!(escape_newlines!(String::from(r"C:\new")), r"C:\\new");
+}
@@ -0,0 +1,64 @@
+use cargo_pkg_info_struct_builder::env_snapshot::{is_glob, snapshot_env};
+use cargo_pkg_info_struct_builder::BuildMetadataInjector;
+use std::env;
+
+#[test]
+fn test_snapshot_env_allow_list() {
+    env::set_var("SNAPSHOT_TEST_DEPLOY_ENV", "staging");
+    env::set_var("SNAPSHOT_TEST_RELEASE_NAME", "r42");
+    env::set_var("SNAPSHOT_TEST_RELEASE_CHANNEL", "beta");
+    env::set_var("SNAPSHOT_TEST_SECRET", "hunter2");
+
+    let allow_list = vec![
+        "SNAPSHOT_TEST_DEPLOY_ENV".to_string(),
+        "SNAPSHOT_TEST_RELEASE_*".to_string(),
+        "SNAPSHOT_TEST_MISSING".to_string(),
+    ];
+
+    assert_eq!(
+        snapshot_env(&allow_list),
+        vec![
+            (
+                "SNAPSHOT_TEST_DEPLOY_ENV".to_string(),
+                "staging".to_string()
+            ),
+            (
+                "SNAPSHOT_TEST_RELEASE_CHANNEL".to_string(),
+                "beta".to_string()
+            ),
+            ("SNAPSHOT_TEST_RELEASE_NAME".to_string(), "r42".to_string()),
+        ]
+    );
+    assert!(snapshot_env(&[]).is_empty());
+}
+
+#[test]
+fn test_is_glob() {
+    assert!(is_glob("RELEASE_*"));
+    assert!(is_glob("BUILD_?"));
+    assert!(!is_glob("DEPLOY_ENV"));
+}
+
+#[test]
+fn test_build_env_values() {
+    env::set_var("INJECT_TEST_SINGLE", "one line");
+    env::set_var("INJECT_TEST_MULTI", "two\r\nlines");
+    env::set_var("INJECT_TEST_SEPARATOR", "a\x1fb");
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let report = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .with_build_env(["INJECT_TEST_*"])
+        .inject()
+        .unwrap();
+
+    // Multi-line values survive the encoding, but the entry separator cannot
+    assert_eq!(
+        report.env_var("BUILD_ENV"),
+        Some("INJECT_TEST_MULTI=two\r\nlines\x1fINJECT_TEST_SINGLE=one line")
+    );
+    assert!(report
+        .warnings
+        .iter()
+        .any(|warning| warning.contains("INJECT_TEST_SEPARATOR")));
+}
@@ -0,0 +1,49 @@
+use cargo_pkg_info_struct_builder::features::{declared_features, resolve_feature_names};
+
+#[test]
+fn test_declared_features() {
+    let manifest: toml::Value = toml::from_str(
+        r#"
+        [package]
+        name = "example"
+
+        [features]
+        default = ["json-output"]
+        json-output = []
+
+        [dependencies]
+        serde = { version = "1", optional = true }
+        log = "0.4"
+
+        [target.'cfg(unix)'.dependencies]
+        nix-extra = { version = "1", optional = true }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        declared_features(&manifest),
+        vec!["default", "json-output", "nix-extra", "serde"]
+    );
+}
+
+#[test]
+fn test_resolve_feature_names() {
+    let declared = vec![
+        "default".to_string(),
+        "json-output".to_string(),
+        "extra_logging".to_string(),
+    ];
+    let env_names = vec![
+        "JSON_OUTPUT".to_string(),
+        "DEFAULT".to_string(),
+        "EXTRA_LOGGING".to_string(),
+        "UNDECLARED".to_string(),
+    ];
+
+    assert_eq!(
+        resolve_feature_names(&env_names, &declared),
+        vec!["default", "extra_logging", "json-output", "undeclared"]
+    );
+    assert!(resolve_feature_names(&[], &declared).is_empty());
+}
@@ -0,0 +1,282 @@
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, GitInfo, GitRepo, VcsSource};
+use std::env;
+use std::fs::{self, File};
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+const COMMIT_A: &str = "1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b";
+const COMMIT_B: &str = "0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e";
+
+/// Serializes the tests in this file that call `inject`, since one of them
+/// changes `CARGO_MANIFEST_DIR`.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Writes a file, creating parent directories as needed.
+fn write(path: &Path, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+/// Runs `git` in `dir`, panicking if it fails.
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(["-c", "commit.gpgsign=false"])
+        .args(args)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+#[test]
+fn test_loose_ref_branch() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let git_dir = temp_dir.path().join(".git");
+    write(&git_dir.join("HEAD"), "ref: refs/heads/main\n");
+    write(&git_dir.join("refs/heads/main"), &format!("{}\n", COMMIT_A));
+
+    // Discovery walks up from nested package directories
+    let package_dir = temp_dir.path().join("crates").join("app");
+    fs::create_dir_all(&package_dir).unwrap();
+
+    let repo = GitRepo::discover(&package_dir).expect("Repository should be found");
+    assert_eq!(repo.work_tree, temp_dir.path());
+
+    let head = repo.head();
+    assert_eq!(head.commit_hash.as_deref(), Some(COMMIT_A));
+    assert_eq!(head.branch.as_deref(), Some("main"));
+
+    assert_eq!(
+        repo.rerun_if_changed_paths(),
+        vec![git_dir.join("HEAD"), git_dir.join("refs/heads/main")]
+    );
+}
+
+#[test]
+fn test_packed_ref_branch() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let git_dir = temp_dir.path().join(".git");
+    write(&git_dir.join("HEAD"), "ref: refs/heads/release\n");
+    write(
+        &git_dir.join("packed-refs"),
+        &format!(
+            "# pack-refs with: peeled fully-peeled sorted\n{} refs/heads/release\n{} refs/tags/v1.0.0\n^{}\n",
+            COMMIT_A, COMMIT_B, COMMIT_A
+        ),
+    );
+
+    let repo = GitRepo::discover(temp_dir.path()).unwrap();
+    let head = repo.head();
+    assert_eq!(head.commit_hash.as_deref(), Some(COMMIT_A));
+    assert_eq!(head.branch.as_deref(), Some("release"));
+
+    // Without a `refs/heads` directory, the missing loose ref itself is watched
+    assert_eq!(
+        repo.rerun_if_changed_paths(),
+        vec![
+            git_dir.join("HEAD"),
+            git_dir.join("refs/heads/release"),
+            git_dir.join("packed-refs"),
+        ]
+    );
+
+    // The next commit creates the loose ref, so its directory is watched
+    fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
+    write(&git_dir.join("index"), "");
+    assert_eq!(
+        repo.rerun_if_changed_paths(),
+        vec![
+            git_dir.join("HEAD"),
+            git_dir.join("refs/heads"),
+            git_dir.join("packed-refs"),
+            git_dir.join("index"),
+        ]
+    );
+}
+
+#[test]
+fn test_detached_head() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    write(
+        &temp_dir.path().join(".git").join("HEAD"),
+        &format!("{}\n", COMMIT_B),
+    );
+
+    let head = GitRepo::discover(temp_dir.path()).unwrap().head();
+    assert_eq!(head.commit_hash.as_deref(), Some(COMMIT_B));
+    assert_eq!(head.branch, None);
+}
+
+#[test]
+fn test_unborn_branch() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    write(
+        &temp_dir.path().join(".git").join("HEAD"),
+        "ref: refs/heads/main\n",
+    );
+
+    let head = GitRepo::discover(temp_dir.path()).unwrap().head();
+    assert_eq!(head.commit_hash, None);
+    assert_eq!(head.branch.as_deref(), Some("main"));
+}
+
+#[test]
+fn test_linked_worktree() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let main_git_dir = temp_dir.path().join("main").join(".git");
+    let worktree_git_dir = main_git_dir.join("worktrees").join("feature");
+    let worktree = temp_dir.path().join("feature");
+
+    write(&main_git_dir.join("HEAD"), "ref: refs/heads/main\n");
+    write(&main_git_dir.join("refs/heads/main"), COMMIT_A);
+    write(&main_git_dir.join("refs/heads/feature"), COMMIT_B);
+    write(&worktree_git_dir.join("HEAD"), "ref: refs/heads/feature\n");
+    write(&worktree_git_dir.join("commondir"), "../..\n");
+    write(
+        &worktree.join(".git"),
+        &format!("gitdir: {}\n", worktree_git_dir.display()),
+    );
+
+    let repo = GitRepo::discover(&worktree).unwrap();
+    assert_eq!(repo.work_tree, worktree);
+    assert_eq!(repo.git_dir, worktree_git_dir);
+
+    let head = repo.head();
+    assert_eq!(head.commit_hash.as_deref(), Some(COMMIT_B));
+    assert_eq!(head.branch.as_deref(), Some("feature"));
+
+    // Branch refs are shared through the main repository
+    assert!(repo
+        .rerun_if_changed_paths()
+        .contains(&repo.common_dir.join("refs/heads/feature")));
+}
+
+#[test]
+fn test_cargo_vcs_info() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    write(
+        &temp_dir.path().join(".cargo_vcs_info.json"),
+        &format!(
+            r#"{{
+  "git": {{
+    "sha1": "{}",
+    "dirty": true
+  }},
+  "path_in_vcs": "crates/app"
+}}"#,
+            COMMIT_A
+        ),
+    );
+
+    let git = GitInfo::from_cargo_vcs_info(temp_dir.path()).expect("VCS info should be read");
+    assert_eq!(git.source, VcsSource::CargoVcsInfo);
+    assert_eq!(git.commit_hash.as_deref(), Some(COMMIT_A));
+    assert_eq!(git.commit_short.as_deref(), Some("1a2b3c4"));
+    assert_eq!(git.dirty, Some(true));
+    assert_eq!(git.path_in_vcs.as_deref(), Some("crates/app"));
+    assert_eq!(git.branch, None);
+}
+
+#[test]
+fn test_cargo_vcs_info_clean() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    write(
+        &temp_dir.path().join(".cargo_vcs_info.json"),
+        &format!(r#"{{"git":{{"sha1":"{}"}},"path_in_vcs":""}}"#, COMMIT_B),
+    );
+
+    let git = GitInfo::from_cargo_vcs_info(temp_dir.path()).unwrap();
+    assert_eq!(git.dirty, Some(false));
+    assert_eq!(git.path_in_vcs.as_deref(), Some(""));
+
+    // Missing files are not an error
+    assert_eq!(
+        GitInfo::from_cargo_vcs_info(&temp_dir.path().join("missing")),
+        None
+    );
+}
+
+#[test]
+fn test_path_in_vcs() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    write(
+        &temp_dir.path().join(".git").join("HEAD"),
+        &format!("{}\n", COMMIT_A),
+    );
+    let package_dir = temp_dir.path().join("crates").join("app");
+
+    let repo = GitRepo::discover(temp_dir.path()).unwrap();
+    assert_eq!(
+        repo.path_in_vcs(&package_dir).as_deref(),
+        Some("crates/app")
+    );
+    assert_eq!(repo.path_in_vcs(temp_dir.path()).as_deref(), Some(""));
+}
+
+#[test]
+fn test_vcs_override() {
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    let report = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .with_vcs_override(GitInfo {
+            commit_hash: Some(COMMIT_B.to_string()),
+            branch: Some("release".to_string()),
+            ..GitInfo::default()
+        })
+        .inject()
+        .unwrap();
+
+    assert_eq!(report.git.as_ref().unwrap().source, VcsSource::Override);
+    assert_eq!(report.env_var("BUILD_VCS_SOURCE"), Some("override"));
+    assert_eq!(report.env_var("BUILD_GIT_COMMIT_HASH"), Some(COMMIT_B));
+    assert_eq!(report.env_var("BUILD_GIT_BRANCH"), Some("release"));
+    assert_eq!(report.env_var("BUILD_GIT_TAG"), None);
+}
+
+#[test]
+fn test_inject_leaves_index_untouched() {
+    let _guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let temp_dir = tempfile::tempdir().unwrap();
+    let manifest = temp_dir.path().join("Cargo.toml");
+    write(
+        &manifest,
+        "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+    );
+    git(temp_dir.path(), &["init", "--quiet"]);
+    git(temp_dir.path(), &["add", "Cargo.toml"]);
+    git(
+        temp_dir.path(),
+        &["commit", "--quiet", "-m", "Initial commit"],
+    );
+
+    // A newer mtime makes the index stale, so `git status` would refresh it
+    File::options()
+        .write(true)
+        .open(&manifest)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(60))
+        .unwrap();
+    let index = temp_dir.path().join(".git").join("index");
+    let index_modified = fs::metadata(&index).unwrap().modified().unwrap();
+
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR");
+    env::set_var("CARGO_MANIFEST_DIR", temp_dir.path());
+    let report = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .with_toolchain(false)
+        .inject();
+    env::set_var("CARGO_MANIFEST_DIR", manifest_dir.unwrap());
+
+    let report = report.unwrap();
+    assert_eq!(report.env_var("BUILD_GIT_DIRTY"), Some("false"));
+    assert_eq!(
+        fs::metadata(&index).unwrap().modified().unwrap(),
+        index_modified
+    );
+}
@@ -1,4 +1,6 @@
-use cargo_pkg_info_struct_builder::inject_build_metadata;
+use cargo_pkg_info_struct_builder::{
+    inject_build_metadata, try_inject_build_metadata, BuildMetadataInjector, InjectError,
+};
 use std::fs;
 
 // Note: Additional tests are performed directly in the `cargo-pkg-info-test-app` workspace.
@@ -62,6 +64,103 @@ fn test_inject_build_metadata_no_mtime_change() {
     syn::parse_file(&contents).expect("Generated Rust file is invalid on second parse!");
 }
 
+#[test]
+fn test_try_inject_build_metadata_report() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dest_path = temp_dir.path().join("nested").join("cargo_pkg_info.rs");
+
+    let report = try_inject_build_metadata(dest_path.to_path_buf()).unwrap();
+    assert_eq!(report.dest_path, dest_path);
+    assert!(report.written, "First injection should write the file");
+    assert!(
+        dest_path.exists(),
+        "Nested destination directory should be created"
+    );
+    assert!(report.env_var("BUILD_TARGET").is_some());
+    assert!(report.env_var("BUILD_TIME_UTC").is_some());
+
+    // This crate does not declare a `license-file`
+    assert!(report.license_path.is_none());
+    assert!(report.license_dest_path.is_none());
+    let contents = fs::read_to_string(&dest_path).unwrap();
+    assert!(!contents.contains("include_str!"));
+
+    let report = try_inject_build_metadata(dest_path.to_path_buf()).unwrap();
+    assert!(!report.written, "Unchanged file should not be rewritten");
+}
+
+#[test]
+fn test_try_inject_build_metadata_invalid_destination() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    // A directory is not a valid destination file
+    let err = try_inject_build_metadata(temp_dir.path().to_path_buf()).unwrap_err();
+    match &err {
+        InjectError::InvalidDestination { path } => assert_eq!(path, temp_dir.path()),
+        other => panic!("Expected InvalidDestination, got {:?}", other),
+    }
+    assert!(err
+        .to_string()
+        .contains(&temp_dir.path().display().to_string()));
+}
+
+#[test]
+fn test_builder_disables_captures() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dest_path = temp_dir.path().join("cargo_pkg_info.rs");
+
+    let report = BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .with_target(false)
+        .with_profile(false)
+        .with_features(false)
+        .with_timestamp(false)
+        .with_license(false)
+        .with_git(false)
+        .with_toolchain(false)
+        .with_ci(false)
+        .inject()
+        .unwrap();
+
+    assert!(report.env_vars.is_empty(), "No metadata should be captured");
+    assert!(report.license_path.is_none());
+
+    // The struct is still generated so accessors return `None`
+    let contents = fs::read_to_string(&dest_path).unwrap();
+    assert!(contents.contains("pub struct CargoPkgInfo"));
+}
+
+#[test]
+fn test_stale_license_copy_removed() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dest_path = temp_dir.path().join("cargo_pkg_info.rs");
+    let license_copy = temp_dir.path().join("cargo_pkg_info_license.txt");
+
+    // Left by an earlier run, before the license was disabled
+    fs::write(&license_copy, "MIT License").unwrap();
+    let report = BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .with_license(false)
+        .inject()
+        .unwrap();
+    assert!(report.license_dest_path.is_none());
+    assert!(
+        !license_copy.exists(),
+        "Stale license copy should be removed"
+    );
+
+    // This crate does not declare a `license-file` either
+    fs::write(&license_copy, "MIT License").unwrap();
+    BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .inject()
+        .unwrap();
+    assert!(
+        !license_copy.exists(),
+        "Stale license copy should be removed"
+    );
+}
+
 /// Ensures that invalid environment variable names are rejected.
 #[test]
 #[should_panic(expected = "Invalid Cargo environment variable name")]
@@ -74,3 +173,72 @@ fn test_invalid_env_var_names() {
 fn test_reject_reserved_cargo_vars() {
     cargo_pkg_info_struct_builder::set_cargo_env_var("CARGO_TEST_VAR", "test value");
 }
+
+#[test]
+fn test_accessors_are_const() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dest_path = temp_dir.path().join("cargo_pkg_info.rs");
+
+    BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .set_custom_var("DEPLOY_ENV", "staging")
+        .inject()
+        .unwrap();
+
+    let contents = fs::read_to_string(&dest_path).unwrap();
+    let file = syn::parse_file(&contents).expect("Generated Rust file is invalid!");
+
+    // These do runtime detection or allocate, so cannot be `const`
+    let runtime_only = [
+        "check_cpu_features",
+        "missing_cpu_features",
+        "build_env_all",
+        "decode_env_value",
+        "split_multi_line_custom_var",
+    ];
+
+    let mut accessors = 0;
+    for item in &file.items {
+        let syn::Item::Impl(item) = item else {
+            continue;
+        };
+        for item in &item.items {
+            let syn::ImplItem::Fn(method) = item else {
+                continue;
+            };
+            let name = method.sig.ident.to_string();
+            if matches!(method.vis, syn::Visibility::Public(_))
+                && !runtime_only.contains(&name.as_str())
+            {
+                assert!(method.sig.constness.is_some(), "`{}` is not const", name);
+                accessors += 1;
+            }
+        }
+    }
+    assert!(accessors > 50);
+
+    assert!(contents.contains("pub const INFO: PkgInfo = PkgInfo {"));
+}
+
+#[test]
+fn test_package_info_trait_impl() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let dest_path = temp_dir.path().join("cargo_pkg_info.rs");
+
+    // Off by default, since it needs the `cargo-pkg-info-runtime` dependency
+    BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .inject()
+        .unwrap();
+    let contents = fs::read_to_string(&dest_path).unwrap();
+    assert!(!contents.contains("PackageInfo for CargoPkgInfo"));
+
+    BuildMetadataInjector::new()
+        .dest(&dest_path)
+        .with_package_info_trait(true)
+        .inject()
+        .unwrap();
+    let contents = fs::read_to_string(&dest_path).unwrap();
+    syn::parse_file(&contents).expect("Generated Rust file is invalid!");
+    assert!(contents.contains("impl ::cargo_pkg_info_runtime::PackageInfo for CargoPkgInfo {"));
+}
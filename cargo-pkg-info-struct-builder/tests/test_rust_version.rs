@@ -0,0 +1,68 @@
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, InjectError, MsrvPolicy, RustVersion};
+use std::path::PathBuf;
+
+#[test]
+fn test_parse_rust_version() {
+    let version = |major, minor, patch| {
+        Some(RustVersion {
+            major,
+            minor,
+            patch,
+        })
+    };
+
+    assert_eq!(RustVersion::parse("1.84"), version(1, 84, 0));
+    assert_eq!(RustVersion::parse("1.84.1"), version(1, 84, 1));
+    assert_eq!(RustVersion::parse("1.86.0-nightly"), version(1, 86, 0));
+    assert_eq!(RustVersion::parse(" 1.70.0\n"), version(1, 70, 0));
+
+    assert_eq!(RustVersion::parse("1"), None);
+    assert_eq!(RustVersion::parse("1.84.0.1"), None);
+    assert_eq!(RustVersion::parse("one.two"), None);
+    assert_eq!(RustVersion::parse(""), None);
+
+    assert_eq!(RustVersion::parse("1.84").unwrap().to_string(), "1.84.0");
+}
+
+#[test]
+fn test_rust_version_ordering() {
+    let parse = |version| RustVersion::parse(version).unwrap();
+
+    assert!(parse("1.84") < parse("1.84.1"));
+    assert!(parse("1.9.0") < parse("1.84.0"));
+    assert!(parse("1.99.9") < parse("2.0.0"));
+
+    // Pre-release suffixes are ignored, as Cargo does for `rust-version`
+    assert_eq!(parse("1.86.0-nightly"), parse("1.86"));
+}
+
+#[test]
+fn test_msrv_not_met_message() {
+    let err = InjectError::MsrvNotMet {
+        path: PathBuf::from("Cargo.toml"),
+        required: "1.84".to_string(),
+        found: "1.80.0".to_string(),
+    };
+
+    assert_eq!(
+        err.to_string(),
+        "rustc 1.80.0 is older than the rust-version 1.84 required by Cargo.toml"
+    );
+}
+
+#[test]
+fn test_msrv_deny_without_rust_version() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    // This package does not declare a `rust-version`, so there is nothing to enforce
+    let report = BuildMetadataInjector::new()
+        .dest(temp_dir.path().join("cargo_pkg_info.rs"))
+        .msrv_policy(MsrvPolicy::Deny)
+        .inject()
+        .unwrap();
+
+    assert!(report
+        .warnings
+        .iter()
+        .all(|warning| !warning.contains("MSRV")));
+}
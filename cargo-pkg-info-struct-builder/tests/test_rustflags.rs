@@ -0,0 +1,61 @@
+use cargo_pkg_info_struct_builder::rustflags::{redact_rustflags, REDACTED};
+
+fn flags(flags: &[&str]) -> Vec<String> {
+    flags.iter().map(|flag| flag.to_string()).collect()
+}
+
+#[test]
+fn test_redact_default_deny_list() {
+    let redacted = redact_rustflags(
+        &flags(&[
+            "-C",
+            "target-cpu=native",
+            "-L",
+            "/home/user/libs",
+            "-L",
+            r"C:\libs",
+            "--remap-path-prefix=~/src=/build",
+            "--cfg",
+            "api_token=\"abc\"",
+            "-Dwarnings",
+        ]),
+        &[],
+    );
+
+    assert_eq!(
+        redacted,
+        flags(&[
+            "-C",
+            "target-cpu=native",
+            "-L",
+            REDACTED,
+            "-L",
+            REDACTED,
+            REDACTED,
+            "--cfg",
+            REDACTED,
+            "-Dwarnings",
+        ])
+    );
+}
+
+#[test]
+fn test_redact_custom_deny_list() {
+    let deny_list = flags(&["*internal*", "target-cpu=?????"]);
+    let redacted = redact_rustflags(
+        &flags(&[
+            "--cfg",
+            "INTERNAL_BUILD",
+            "-C",
+            "target-cpu=znver",
+            "-C",
+            "target-cpu=native",
+        ]),
+        &deny_list,
+    );
+
+    assert_eq!(
+        redacted,
+        flags(&["--cfg", REDACTED, "-C", REDACTED, "-C", "target-cpu=native"])
+    );
+}
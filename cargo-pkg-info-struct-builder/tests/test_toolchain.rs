@@ -0,0 +1,64 @@
+use cargo_pkg_info_struct_builder::toolchain::parse_cargo_version;
+use cargo_pkg_info_struct_builder::RustcInfo;
+
+#[test]
+fn test_parse_rustc_verbose_version() {
+    let rustc = RustcInfo::parse(
+        "rustc 1.84.0 (9fc6b4312 2025-01-07)\n\
+         binary: rustc\n\
+         commit-hash: 9fc6b43126469e3858e2fe86cafb4f0fd5068869\n\
+         commit-date: 2025-01-07\n\
+         host: x86_64-unknown-linux-gnu\n\
+         release: 1.84.0\n\
+         LLVM version: 19.1.5\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        rustc,
+        RustcInfo {
+            version: "1.84.0".to_string(),
+            commit_hash: Some("9fc6b43126469e3858e2fe86cafb4f0fd5068869".to_string()),
+            channel: "stable".to_string(),
+            host: Some("x86_64-unknown-linux-gnu".to_string()),
+            llvm_version: Some("19.1.5".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_parse_rustc_nightly_without_llvm() {
+    let rustc = RustcInfo::parse(
+        "rustc 1.86.0-nightly\n\
+         binary: rustc\n\
+         commit-hash: unknown\n\
+         commit-date: unknown\n\
+         host: aarch64-apple-darwin\n\
+         release: 1.86.0-nightly\n",
+    )
+    .unwrap();
+
+    assert_eq!(rustc.version, "1.86.0-nightly");
+    assert_eq!(rustc.channel, "nightly");
+    assert_eq!(rustc.commit_hash, None);
+    assert_eq!(rustc.llvm_version, None);
+
+    assert_eq!(RustcInfo::parse("rustc 1.84.0"), None);
+}
+
+#[test]
+fn test_parse_cargo_version() {
+    assert_eq!(
+        parse_cargo_version("cargo 1.84.0 (66221abde 2024-11-19)\n"),
+        Some("1.84.0".to_string())
+    );
+    assert_eq!(parse_cargo_version("rustc 1.84.0"), None);
+}
+
+#[test]
+fn test_probe_toolchain() {
+    // The tests are run by Cargo, so both tools are available
+    let rustc = RustcInfo::probe().unwrap();
+    assert!(rustc.version.starts_with("1."));
+    assert!(cargo_pkg_info_struct_builder::toolchain::probe_cargo_version().is_ok());
+}
@@ -0,0 +1,53 @@
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, Visibility};
+use std::fs;
+
+/// Returns the visibility of each top-level item that has one, as written.
+fn item_visibilities(contents: &str) -> Vec<String> {
+    let file = syn::parse_file(contents).expect("Generated Rust file is invalid!");
+
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Struct(item) => Some(&item.vis),
+            syn::Item::Enum(item) => Some(&item.vis),
+            syn::Item::Mod(item) => Some(&item.vis),
+            _ => None,
+        })
+        .map(|vis| match vis {
+            syn::Visibility::Public(_) => "pub".to_string(),
+            syn::Visibility::Restricted(restricted) if restricted.path.is_ident("crate") => {
+                "pub(crate)".to_string()
+            }
+            syn::Visibility::Restricted(_) => "restricted".to_string(),
+            syn::Visibility::Inherited => "private".to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_visibility() {
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    for visibility in [Visibility::Public, Visibility::Crate] {
+        let dest_path = temp_dir.path().join("cargo_pkg_info.rs");
+        BuildMetadataInjector::new()
+            .dest(&dest_path)
+            .visibility(visibility)
+            .set_custom_var("DEPLOY_ENV", "staging")
+            .inject()
+            .unwrap();
+
+        let contents = fs::read_to_string(&dest_path).unwrap();
+        let visibilities = item_visibilities(&contents);
+        assert!(visibilities.len() > 1);
+        assert!(
+            visibilities.iter().all(|vis| vis == visibility.as_str()),
+            "Expected only `{}` items, got {:?}",
+            visibility.as_str(),
+            visibilities
+        );
+
+        // Exported macros would collide when the file is included more than once
+        assert!(!contents.contains("#[macro_export]"));
+    }
+}
@@ -1,21 +1,44 @@
-use cargo_pkg_info_struct_builder::{
-    inject_build_metadata::{inject_build_metadata, set_multi_line_cargo_env_var},
-    set_cargo_env_var,
-};
+use cargo_pkg_info_struct_builder::{BuildMetadataInjector, MsrvPolicy, Visibility};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 fn main() {
-    set_cargo_env_var("TEST_CUSTOM_ENV_VAR", "TEST_RESULT");
-    set_multi_line_cargo_env_var(
-        "TEST_MULTI_LINE_CUSTOM_ENV_VAR",
-        r#"
-        Some multi-line environment variable
-        
-        Level 1
-            Level 2
-                Level 3
-        "#,
-    );
+    BuildMetadataInjector::new()
+        .dest(Path::new("src").join("cargo_pkg_info.rs"))
+        .msrv_policy(MsrvPolicy::Deny)
+        .with_rustflags(true)
+        .with_package_info_trait(true)
+        .with_build_env(["DEPLOY_ENV", "CARGO_PKG_VERSION_*"])
+        .set_custom_var("TEST_CUSTOM_ENV_VAR", "TEST_RESULT")
+        .set_custom_var("TEST_ROUND_TRIP_VAR", "C:\\new\\raw = é ✓\r\nline2\\n")
+        .set_multi_line_custom_var(
+            "TEST_MULTI_LINE_CUSTOM_ENV_VAR",
+            r#"
+            Some multi-line environment variable
+            
+            Level 1
+                Level 2
+                    Level 3
+            "#,
+        )
+        .set_custom_value("MAX_CONNECTIONS", 64u32)
+        .set_custom_value("RETRY_OFFSET", -3i64)
+        .set_custom_value("ENABLE_METRICS", true)
+        .set_custom_value("SAMPLE_RATE", 0.25)
+        .set_custom_value("REGIONS", vec!["eu-west", "us-east"])
+        .set_custom_value(
+            "TIMEOUTS_MS",
+            BTreeMap::from([("connect", 500u64), ("read", 2_000u64)]),
+        )
+        .inject()
+        .expect("Failed to inject build metadata");
 
-    inject_build_metadata(Path::new("src").join("cargo_pkg_info.rs").to_path_buf());
+    // A second, crate-private copy checks that generated files can coexist
+    BuildMetadataInjector::new()
+        .dest(Path::new("src").join("internal_pkg_info.rs"))
+        .visibility(Visibility::Crate)
+        .with_toolchain(false)
+        .set_custom_var("INTERNAL_CUSTOM_ENV_VAR", "INTERNAL_RESULT")
+        .inject()
+        .expect("Failed to inject internal build metadata");
 }
@@ -0,0 +1,1400 @@
+//------------------------------------------------------------------------------
+// This file is automatically generated by `cargo-pkg-info-struct-builder`.
+//
+// DO NOT EDIT THIS FILE MANUALLY. ANY CHANGES WILL BE OVERWRITTEN.
+//
+// It contains a struct `CargoPkgInfo` that provides access to package metadata
+// set by Cargo at compile time, including versioning, authors, license
+// information, and build details. The values are obtained from environment
+// variables defined in the Cargo.toml file and passed during the build process.
+//
+// For more information, see:
+// - Cargo Environment Variables: <https://doc.rust-lang.org/cargo/reference/environment-variables.html#environment-variables-cargo-sets-for-crates>
+// - Repository: <https://github.com/jzombie/rust-cargo-pkg-info-struct-builder>
+//------------------------------------------------------------------------------
+
+#[allow(dead_code)]
+pub(crate) struct CargoPkgInfo {}
+
+/// Where the embedded build timestamp came from.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BuildTimeSource {
+    /// The `SOURCE_DATE_EPOCH` environment variable (reproducible builds).
+    SourceDateEpoch,
+    /// The commit time of the built commit.
+    GitCommit,
+    /// The system clock when the build script ran.
+    SystemClock,
+}
+
+/// The Cargo profile the package was built with.
+///
+/// Custom profiles report the built-in profile they inherit from.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BuildProfile {
+    /// The `dev` profile (or one inheriting from it).
+    Debug,
+    /// The `release` profile (or one inheriting from it).
+    Release,
+}
+
+/// The optimization level (`opt-level`) the package was built with.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OptLevel {
+    /// No optimizations (`0`).
+    O0,
+    /// Basic optimizations (`1`).
+    O1,
+    /// Some optimizations (`2`).
+    O2,
+    /// All optimizations (`3`).
+    O3,
+    /// Optimize for binary size (`"s"`).
+    Os,
+    /// Optimize for binary size, also turning off loop vectorization (`"z"`).
+    Oz,
+}
+
+/// The CPU architecture of the build target (`target_arch`).
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TargetArch {
+    X86,
+    X86_64,
+    Arm,
+    Aarch64,
+    Riscv32,
+    Riscv64,
+    Wasm32,
+    Wasm64,
+    Powerpc,
+    Powerpc64,
+    Mips,
+    Mips64,
+    S390x,
+    Loongarch64,
+    /// Any other architecture, by its `target_arch` name.
+    Other(&'static str),
+}
+
+/// The operating system of the build target (`target_os`).
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TargetOs {
+    Linux,
+    Windows,
+    MacOs,
+    Ios,
+    Android,
+    FreeBsd,
+    NetBsd,
+    OpenBsd,
+    DragonFly,
+    Solaris,
+    Illumos,
+    Fuchsia,
+    Wasi,
+    /// Any other operating system (including `none`), by its `target_os` name.
+    Other(&'static str),
+}
+
+/// The byte order of the build target (`target_endian`).
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Endian {
+    Little,
+    Big,
+}
+
+/// The build target, decomposed from its `cfg` values.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TargetInfo {
+    pub arch: TargetArch,
+    pub os: TargetOs,
+    /// The target environment (`target_env`), e.g. `gnu` or `msvc`; empty if none.
+    pub env: &'static str,
+    /// The target vendor (`target_vendor`), e.g. `unknown` or `apple`.
+    pub vendor: &'static str,
+    pub endian: Endian,
+    /// The pointer width in bits (`target_pointer_width`).
+    pub pointer_width: u32,
+    /// The target families (`target_family`), e.g. `["unix"]`.
+    pub family: &'static [&'static str],
+    /// The atomic widths supported by the target (`target_has_atomic`), e.g.
+    /// `["8", "16", "32", "64", "ptr"]`.
+    pub has_atomic: &'static [&'static str],
+}
+
+/// Where the embedded VCS metadata came from.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VcsSource {
+    /// Read from a live git repository.
+    Git,
+    /// Read from the `.cargo_vcs_info.json` file of a packaged crate.
+    CargoVcsInfo,
+    /// Supplied explicitly by the build script.
+    Override,
+}
+
+/// A Rust release version, such as the package's `rust-version`.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct RustVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+#[allow(dead_code)]
+impl RustVersion {
+    /// Parses a version such as `1.84`, `1.84.1` or `1.86.0-nightly`, treating a
+    /// missing patch component as `0` and ignoring any pre-release suffix.
+    const fn parse(version: &str) -> Option<RustVersion> {
+        let bytes = version.as_bytes();
+        let mut start = 0;
+        let mut end = bytes.len();
+        while start < end && bytes[start].is_ascii_whitespace() {
+            start += 1;
+        }
+        while end > start && bytes[end - 1].is_ascii_whitespace() {
+            end -= 1;
+        }
+
+        let mut parts = [0u32; 3];
+        let mut count = 0;
+        let mut digits = 0;
+        let mut i = start;
+
+        while i <= end {
+            if i == end || bytes[i] == b'-' || bytes[i] == b'.' {
+                if digits == 0 {
+                    return None;
+                }
+                count += 1;
+                digits = 0;
+
+                if i == end || bytes[i] == b'-' {
+                    break;
+                }
+            } else if bytes[i].is_ascii_digit() && count < parts.len() {
+                parts[count] = match push_digit(parts[count] as u64, bytes[i]) {
+                    Some(part) if part <= u32::MAX as u64 => part as u32,
+                    _ => return None,
+                };
+                digits += 1;
+            } else {
+                return None;
+            }
+            i += 1;
+        }
+
+        if count < 2 {
+            return None;
+        }
+
+        Some(RustVersion {
+            major: parts[0],
+            minor: parts[1],
+            patch: parts[2],
+        })
+    }
+
+    /// Returns `true` if this version is `major.minor.patch` or newer.
+    const fn is_at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+        if self.major != major {
+            return self.major > major;
+        }
+        if self.minor != minor {
+            return self.minor > minor;
+        }
+        self.patch >= patch
+    }
+}
+
+/// A package version, parsed from Cargo's `CARGO_PKG_VERSION_*` variables.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// The pre-release identifier, e.g. `beta`; empty for releases.
+    pub pre: &'static str,
+}
+
+/// The package metadata gathered into a single value; see [`INFO`].
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PkgInfo {
+    pub name: &'static str,
+    pub version: Version,
+    /// The authors, separated by `:` as Cargo sets them.
+    pub authors: &'static str,
+    pub description: &'static str,
+    pub homepage: &'static str,
+    pub repository: &'static str,
+    /// The license expression (`license`); empty if only a `license-file` is set.
+    pub license: &'static str,
+    pub license_content: Option<&'static str>,
+    pub rust_version: Option<RustVersion>,
+    pub build_target: Option<&'static str>,
+    pub build_time_utc: Option<u64>,
+    pub build_profile: Option<BuildProfile>,
+    pub git_commit_hash: Option<&'static str>,
+    pub rustc_version: Option<RustVersion>,
+}
+
+/// Returns the value of an `option_env!`, or an empty string if it is unset.
+#[allow(dead_code)]
+const fn env_or_empty(env_data: Option<&'static str>) -> &'static str {
+    match env_data {
+        Some(value) => value,
+        None => "",
+    }
+}
+
+/// Compares two strings in a `const` context.
+#[allow(dead_code)]
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Looks up a value by name in a `const` context, e.g. to map `"debug"` to
+/// `BuildProfile::Debug`.
+#[allow(dead_code)]
+const fn lookup<T: Copy>(value: Option<&str>, table: &[(&str, T)]) -> Option<T> {
+    let Some(value) = value else {
+        return None;
+    };
+
+    let mut i = 0;
+    while i < table.len() {
+        if str_eq(value, table[i].0) {
+            return Some(table[i].1);
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Appends a decimal digit to `n`, returning `None` on overflow.
+#[allow(dead_code)]
+const fn push_digit(n: u64, digit: u8) -> Option<u64> {
+    match n.checked_mul(10) {
+        Some(n) => n.checked_add((digit - b'0') as u64),
+        None => None,
+    }
+}
+
+/// Parses a decimal number in a `const` context, returning `None` if it is
+/// unset, empty, contains other characters or overflows.
+#[allow(dead_code)]
+const fn parse_u64(value: Option<&str>) -> Option<u64> {
+    let Some(value) = value else {
+        return None;
+    };
+    let bytes = value.as_bytes();
+    if bytes.is_empty() {
+        return None;
+    }
+
+    let mut n = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        n = match push_digit(n, bytes[i]) {
+            Some(n) => n,
+            None => return None,
+        };
+        i += 1;
+    }
+
+    Some(n)
+}
+
+/// Parses a decimal `u32` in a `const` context; see [`parse_u64`].
+#[allow(dead_code)]
+const fn parse_u32(value: Option<&str>) -> Option<u32> {
+    match parse_u64(value) {
+        Some(n) if n <= u32::MAX as u64 => Some(n as u32),
+        _ => None,
+    }
+}
+
+/// Parses a `true`/`false` value in a `const` context.
+#[allow(dead_code)]
+const fn parse_bool(value: Option<&str>) -> Option<bool> {
+    lookup(value, &[("true", true), ("false", false)])
+}
+
+/// Splits a `NAME=value` entry at the first `=` in a `const` context.
+#[allow(dead_code)]
+const fn split_entry(entry: &'static str) -> Option<(&'static str, &'static str)> {
+    let bytes = entry.as_bytes();
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            let (name, rest) = bytes.split_at(i);
+            let (_, value) = rest.split_at(1);
+
+            // Splitting at an ASCII `=` keeps both halves valid UTF-8
+            return match (core::str::from_utf8(name), core::str::from_utf8(value)) {
+                (Ok(name), Ok(value)) => Some((name, value)),
+                _ => None,
+            };
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Counts the non-empty items in a `sep`-separated list.
+#[allow(dead_code)]
+const fn count_list_items(list: &str, sep: u8) -> usize {
+    let bytes = list.as_bytes();
+    let mut count = 0;
+    let mut start = 0;
+    let mut i = 0;
+
+    while i <= bytes.len() {
+        if i == bytes.len() || bytes[i] == sep {
+            if i > start {
+                count += 1;
+            }
+            start = i + 1;
+        }
+        i += 1;
+    }
+
+    count
+}
+
+/// Splits a `sep`-separated list into its `N` non-empty items at compile time,
+/// so that list accessors can return `&'static` slices without allocating.
+///
+/// `N` must come from [`count_list_items`] for the same list.
+#[allow(dead_code)]
+const fn split_list<const N: usize>(list: &'static str, sep: u8) -> [&'static str; N] {
+    let mut items = [""; N];
+    let mut rest = list.as_bytes();
+    let mut n = 0;
+
+    while n < N {
+        let mut i = 0;
+        while i < rest.len() && rest[i] != sep {
+            i += 1;
+        }
+
+        let (item, tail) = rest.split_at(i);
+        if !item.is_empty() {
+            // Splitting at an ASCII separator keeps each item valid UTF-8
+            items[n] = match core::str::from_utf8(item) {
+                Ok(item) => item,
+                Err(_) => panic!("list item is not valid UTF-8"),
+            };
+            n += 1;
+        }
+
+        rest = if tail.is_empty() {
+            tail
+        } else {
+            tail.split_at(1).1
+        };
+    }
+
+    items
+}
+
+/// The continuous integration service that ran the build.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CiProvider {
+    GitHubActions,
+    GitLabCi,
+    Buildkite,
+    CircleCi,
+    Jenkins,
+    TravisCi,
+    AzurePipelines,
+    BitbucketPipelines,
+    TeamCity,
+    /// An unrecognized service that sets `CI=true`.
+    Generic,
+}
+
+/// Returns the length of a value encoded by `set_cargo_env_var` once decoded.
+#[allow(dead_code)]
+const fn decoded_len(encoded: &str) -> usize {
+    let bytes = encoded.as_bytes();
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        // Recognized escapes are two bytes long and decode to one
+        let escaped = bytes[i] == b'\\' && i + 1 < bytes.len();
+        if escaped && matches!(bytes[i + 1], b'\\' | b'n' | b'r') {
+            i += 2;
+        } else {
+            i += 1;
+        }
+        len += 1;
+    }
+
+    len
+}
+
+/// Decodes a value encoded by `set_cargo_env_var` at compile time.
+///
+/// Line feeds and carriage returns are written as `\n` and `\r`, and
+/// backslashes are doubled. Unrecognized escape sequences are kept as-is.
+///
+/// `N` must come from [`decoded_len`] for the same value.
+#[allow(dead_code)]
+const fn decode_env_bytes<const N: usize>(encoded: &str) -> [u8; N] {
+    let bytes = encoded.as_bytes();
+    let mut decoded = [0u8; N];
+    let mut i = 0;
+    let mut n = 0;
+
+    while n < N {
+        decoded[n] = if bytes[i] == b'\\' && i + 1 < bytes.len() {
+            match bytes[i + 1] {
+                b'\\' => {
+                    i += 1;
+                    b'\\'
+                }
+                b'n' => {
+                    i += 1;
+                    b'\n'
+                }
+                b'r' => {
+                    i += 1;
+                    b'\r'
+                }
+                _ => b'\\',
+            }
+        } else {
+            bytes[i]
+        };
+        i += 1;
+        n += 1;
+    }
+
+    decoded
+}
+
+/// Converts decoded bytes back to a string at compile time.
+#[allow(dead_code)]
+const fn decoded_str(bytes: &'static [u8]) -> &'static str {
+    // Only ASCII escapes are replaced, so decoding keeps the text valid UTF-8
+    match core::str::from_utf8(bytes) {
+        Ok(value) => value,
+        Err(_) => panic!("decoded value is not valid UTF-8"),
+    }
+}
+
+/// Reads an environment variable set by `set_cargo_env_var`, decoding it at
+/// compile time so the result is a `&'static str` without any runtime work.
+macro_rules! decoded_env {
+    ($name:literal) => {{
+        const ENCODED: Option<&str> = option_env!($name);
+        const BYTES: [u8; decoded_len(env_or_empty(ENCODED))] =
+            decode_env_bytes(env_or_empty(ENCODED));
+
+        match ENCODED {
+            Some(_) => Some(decoded_str(&BYTES)),
+            None => None,
+        }
+    }};
+}
+
+impl CargoPkgInfo {
+    /// Returns the package name.
+    #[allow(dead_code)]
+    pub const fn pkg_name() -> Option<&'static str> {
+        option_env!("CARGO_PKG_NAME")
+    }
+
+    /// Returns the crate name.
+    #[allow(dead_code)]
+    pub const fn crate_name() -> Option<&'static str> {
+        option_env!("CARGO_CRATE_NAME")
+    }
+
+    /// Returns the package version.
+    #[allow(dead_code)]
+    pub const fn pkg_version() -> Option<&'static str> {
+        option_env!("CARGO_PKG_VERSION")
+    }
+
+    /// Returns the version derived from `git describe` (e.g. `1.2.3-beta.14+g1a2b3c4.dirty`),
+    /// if enabled in the build script.
+    #[allow(dead_code)]
+    pub const fn describe_version() -> Option<&'static str> {
+        decoded_env!("BUILD_DESCRIBE_VERSION")
+    }
+
+    /// Returns the major version of the package.
+    #[allow(dead_code)]
+    pub const fn version_major() -> Option<&'static str> {
+        option_env!("CARGO_PKG_VERSION_MAJOR")
+    }
+
+    /// Returns the major version of the package as a number.
+    #[allow(dead_code)]
+    pub const fn version_major_numeric() -> Option<u32> {
+        const VALUE: Option<u32> = parse_u32(option_env!("CARGO_PKG_VERSION_MAJOR"));
+        VALUE
+    }
+
+    /// Returns the minor version of the package.
+    #[allow(dead_code)]
+    pub const fn version_minor() -> Option<&'static str> {
+        option_env!("CARGO_PKG_VERSION_MINOR")
+    }
+
+    /// Returns the minor version of the package as a number.
+    #[allow(dead_code)]
+    pub const fn version_minor_numeric() -> Option<u32> {
+        const VALUE: Option<u32> = parse_u32(option_env!("CARGO_PKG_VERSION_MINOR"));
+        VALUE
+    }
+
+    /// Returns the patch version of the pacakge.
+    #[allow(dead_code)]
+    pub const fn version_patch() -> Option<&'static str> {
+        option_env!("CARGO_PKG_VERSION_PATCH")
+    }
+
+    /// Returns the patch version of the pacakge as a number.
+    #[allow(dead_code)]
+    pub const fn version_patch_numeric() -> Option<u32> {
+        const VALUE: Option<u32> = parse_u32(option_env!("CARGO_PKG_VERSION_PATCH"));
+        VALUE
+    }
+
+    /// Returns the pre-release version of the package.
+    #[allow(dead_code)]
+    pub const fn version_pre() -> Option<&'static str> {
+        option_env!("CARGO_PKG_VERSION_PRE")
+    }
+
+    /// Returns the authors of the package.
+    #[allow(dead_code)]
+    pub const fn authors() -> Option<&'static str> {
+        option_env!("CARGO_PKG_AUTHORS")
+    }
+
+    /// Returns the description of the package.
+    #[allow(dead_code)]
+    pub const fn description() -> Option<&'static str> {
+        option_env!("CARGO_PKG_DESCRIPTION")
+    }
+
+    /// Returns the homepage URL of the package.
+    #[allow(dead_code)]
+    pub const fn homepage() -> Option<&'static str> {
+        option_env!("CARGO_PKG_HOMEPAGE")
+    }
+
+    /// Returns the repository URL of the package.
+    #[allow(dead_code)]
+    pub const fn repository() -> Option<&'static str> {
+        option_env!("CARGO_PKG_REPOSITORY")
+    }
+
+    /// Returns the license type of the package.
+    #[allow(dead_code)]
+    pub const fn license() -> Option<&'static str> {
+        option_env!("CARGO_PKG_LICENSE")
+    }
+
+    /// Returns the Rust version required by the package.
+    #[allow(dead_code)]
+    pub const fn rust_version() -> Option<&'static str> {
+        option_env!("CARGO_PKG_RUST_VERSION")
+    }
+
+    /// Returns the Rust version required by the package, parsed into its components.
+    #[allow(dead_code)]
+    pub const fn rust_version_parsed() -> Option<RustVersion> {
+        const VALUE: Option<RustVersion> = match option_env!("CARGO_PKG_RUST_VERSION") {
+            Some(version) => RustVersion::parse(version),
+            None => None,
+        };
+        VALUE
+    }
+
+    /// Returns the path to the README file.
+    #[allow(dead_code)]
+    pub const fn readme_path() -> Option<&'static str> {
+        option_env!("CARGO_PKG_README")
+    }
+
+    /// Returns the build target (architecture/platform).
+    #[allow(dead_code)]
+    pub const fn build_target() -> Option<&'static str> {
+        decoded_env!("BUILD_TARGET")
+    }
+
+    /// Returns the build target decomposed into its architecture, OS, endianness, etc.
+    #[allow(dead_code)]
+    pub const fn target_info() -> Option<TargetInfo> {
+        let Some(arch) = decoded_env!("BUILD_TARGET_ARCH") else {
+            return None;
+        };
+        let arch = match lookup(
+            Some(arch),
+            &[
+                ("x86", TargetArch::X86),
+                ("x86_64", TargetArch::X86_64),
+                ("arm", TargetArch::Arm),
+                ("aarch64", TargetArch::Aarch64),
+                ("riscv32", TargetArch::Riscv32),
+                ("riscv64", TargetArch::Riscv64),
+                ("wasm32", TargetArch::Wasm32),
+                ("wasm64", TargetArch::Wasm64),
+                ("powerpc", TargetArch::Powerpc),
+                ("powerpc64", TargetArch::Powerpc64),
+                ("mips", TargetArch::Mips),
+                ("mips64", TargetArch::Mips64),
+                ("s390x", TargetArch::S390x),
+                ("loongarch64", TargetArch::Loongarch64),
+            ],
+        ) {
+            Some(arch) => arch,
+            None => TargetArch::Other(arch),
+        };
+
+        let Some(os) = decoded_env!("BUILD_TARGET_OS") else {
+            return None;
+        };
+        let os = match lookup(
+            Some(os),
+            &[
+                ("linux", TargetOs::Linux),
+                ("windows", TargetOs::Windows),
+                ("macos", TargetOs::MacOs),
+                ("ios", TargetOs::Ios),
+                ("android", TargetOs::Android),
+                ("freebsd", TargetOs::FreeBsd),
+                ("netbsd", TargetOs::NetBsd),
+                ("openbsd", TargetOs::OpenBsd),
+                ("dragonfly", TargetOs::DragonFly),
+                ("solaris", TargetOs::Solaris),
+                ("illumos", TargetOs::Illumos),
+                ("fuchsia", TargetOs::Fuchsia),
+                ("wasi", TargetOs::Wasi),
+            ],
+        ) {
+            Some(os) => os,
+            None => TargetOs::Other(os),
+        };
+
+        let Some(endian) = lookup(
+            decoded_env!("BUILD_TARGET_ENDIAN"),
+            &[("little", Endian::Little), ("big", Endian::Big)],
+        ) else {
+            return None;
+        };
+
+        let Some(pointer_width) = parse_u32(decoded_env!("BUILD_TARGET_POINTER_WIDTH")) else {
+            return None;
+        };
+
+        Some(TargetInfo {
+            arch,
+            os,
+            env: env_or_empty(decoded_env!("BUILD_TARGET_ENV")),
+            vendor: env_or_empty(decoded_env!("BUILD_TARGET_VENDOR")),
+            endian,
+            pointer_width,
+            family: Self::target_family(),
+            has_atomic: Self::target_has_atomic(),
+        })
+    }
+
+    /// Returns the target families (e.g. `["unix"]` or `["unix", "wasm"]`); empty if not captured.
+    #[allow(dead_code)]
+    pub const fn target_family() -> &'static [&'static str] {
+        const LIST: &str = env_or_empty(decoded_env!("BUILD_TARGET_FAMILY"));
+        const ITEMS: [&str; count_list_items(LIST, b',')] = split_list(LIST, b',');
+        &ITEMS
+    }
+
+    /// Returns the atomic widths supported by the target (e.g. `["8", "16", "32", "64", "ptr"]`).
+    #[allow(dead_code)]
+    pub const fn target_has_atomic() -> &'static [&'static str] {
+        const LIST: &str = env_or_empty(decoded_env!("BUILD_TARGET_HAS_ATOMIC"));
+        const ITEMS: [&str; count_list_items(LIST, b',')] = split_list(LIST, b',');
+        &ITEMS
+    }
+
+    /// Returns the CPU features the package was compiled for (e.g. `["fxsr", "sse", "sse2"]`).
+    #[allow(dead_code)]
+    pub const fn target_features() -> &'static [&'static str] {
+        const LIST: &str = env_or_empty(decoded_env!("BUILD_TARGET_FEATURES"));
+        const ITEMS: [&str; count_list_items(LIST, b',')] = split_list(LIST, b',');
+        &ITEMS
+    }
+
+    /// Returns the compiled-in CPU features that the running CPU lacks.
+    ///
+    /// Running a binary built for features the CPU does not support (e.g. via
+    /// `-C target-cpu=native`) can crash with `SIGILL`, so call this early and
+    /// fail with a clear message if the result is not empty.
+    ///
+    /// Features that cannot be detected at runtime on this architecture are
+    /// assumed to be present.
+    #[allow(dead_code)]
+    pub fn check_cpu_features() -> Vec<&'static str> {
+        Self::missing_cpu_features(Self::target_features(), Self::cpu_feature_detected)
+    }
+
+    /// Returns the `compiled` features that `detect` reports as missing.
+    ///
+    /// `detect` returns `None` for features it cannot check, which are assumed
+    /// to be present.
+    #[allow(dead_code)]
+    pub fn missing_cpu_features(
+        compiled: &[&'static str],
+        detect: impl Fn(&str) -> Option<bool>,
+    ) -> Vec<&'static str> {
+        compiled
+            .iter()
+            .copied()
+            .filter(|feature| detect(feature) == Some(false))
+            .collect()
+    }
+
+    /// Detects a CPU feature at runtime, returning `None` if it cannot be detected.
+    ///
+    /// This queries `cpuid` directly, since `is_x86_feature_detected!` reports
+    /// compiled-in features as present without checking the CPU.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    #[allow(unused_unsafe)]
+    fn cpu_feature_detected(feature: &str) -> Option<bool> {
+        #[cfg(target_arch = "x86")]
+        use core::arch::x86::{__cpuid, __cpuid_count, _xgetbv, CpuidResult};
+        #[cfg(target_arch = "x86_64")]
+        use core::arch::x86_64::{__cpuid, __cpuid_count, _xgetbv, CpuidResult};
+
+        /// Reads the `XCR0` register, which records the state the OS saves.
+        #[target_feature(enable = "xsave")]
+        unsafe fn xcr0() -> u64 {
+            unsafe { _xgetbv(0) }
+        }
+
+        let bit = |register: u32, n: u32| register & (1 << n) != 0;
+        let empty = CpuidResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        };
+
+        // SAFETY: `cpuid` is available on every CPU that Rust's x86 targets
+        // support, and each leaf is only queried if the CPU reports it
+        let (leaf1, leaf7, leaf_d, ext1) = unsafe {
+            let max_leaf = __cpuid(0).eax;
+            let max_ext_leaf = __cpuid(0x8000_0000).eax;
+            (
+                __cpuid(1),
+                if max_leaf >= 7 {
+                    __cpuid_count(7, 0)
+                } else {
+                    empty
+                },
+                if max_leaf >= 0xd {
+                    __cpuid_count(0xd, 1)
+                } else {
+                    empty
+                },
+                if max_ext_leaf >= 0x8000_0001 {
+                    __cpuid(0x8000_0001)
+                } else {
+                    empty
+                },
+            )
+        };
+
+        // AVX and AVX-512 registers are only usable if the OS saves them
+        let xcr0 = if bit(leaf1.ecx, 27) {
+            // SAFETY: `OSXSAVE` is set, so `xgetbv` is available
+            unsafe { xcr0() }
+        } else {
+            0
+        };
+        let os_avx = xcr0 & 0b110 == 0b110;
+        let os_avx512 = os_avx && xcr0 & 0b1110_0000 == 0b1110_0000;
+
+        let detected = match feature {
+            "adx" => bit(leaf7.ebx, 19),
+            "aes" => bit(leaf1.ecx, 25),
+            "avx" => os_avx && bit(leaf1.ecx, 28),
+            "avx2" => os_avx && bit(leaf7.ebx, 5),
+            "avx512bw" => os_avx512 && bit(leaf7.ebx, 30),
+            "avx512cd" => os_avx512 && bit(leaf7.ebx, 28),
+            "avx512dq" => os_avx512 && bit(leaf7.ebx, 17),
+            "avx512f" => os_avx512 && bit(leaf7.ebx, 16),
+            "avx512fp16" => os_avx512 && bit(leaf7.edx, 23),
+            "avx512vl" => os_avx512 && bit(leaf7.ebx, 31),
+            "bmi1" => bit(leaf7.ebx, 3),
+            "bmi2" => bit(leaf7.ebx, 8),
+            "cmpxchg16b" => bit(leaf1.ecx, 13),
+            "f16c" => os_avx && bit(leaf1.ecx, 29),
+            "fma" => os_avx && bit(leaf1.ecx, 12),
+            "fxsr" => bit(leaf1.edx, 24),
+            "lzcnt" => bit(ext1.ecx, 5),
+            "pclmulqdq" => bit(leaf1.ecx, 1),
+            "popcnt" => bit(leaf1.ecx, 23),
+            "rdrand" => bit(leaf1.ecx, 30),
+            "rdseed" => bit(leaf7.ebx, 18),
+            "sha" => bit(leaf7.ebx, 29),
+            "sse" => bit(leaf1.edx, 25),
+            "sse2" => bit(leaf1.edx, 26),
+            "sse3" => bit(leaf1.ecx, 0),
+            "sse4.1" => bit(leaf1.ecx, 19),
+            "sse4.2" => bit(leaf1.ecx, 20),
+            "sse4a" => bit(ext1.ecx, 6),
+            "ssse3" => bit(leaf1.ecx, 9),
+            "tbm" => bit(ext1.ecx, 21),
+            "xsave" => bit(leaf1.ecx, 26),
+            "xsavec" => bit(leaf_d.eax, 1),
+            "xsaveopt" => bit(leaf_d.eax, 0),
+            "xsaves" => bit(leaf_d.eax, 3),
+            _ => return None,
+        };
+
+        Some(detected)
+    }
+
+    /// Detects a CPU feature at runtime, returning `None` if it cannot be detected.
+    ///
+    /// This reads the kernel's `AT_HWCAP` bits directly, since
+    /// `is_aarch64_feature_detected!` reports compiled-in features as present
+    /// without checking the CPU.
+    #[cfg(all(
+        target_arch = "aarch64",
+        any(target_os = "linux", target_os = "android")
+    ))]
+    fn cpu_feature_detected(feature: &str) -> Option<bool> {
+        const AT_HWCAP: usize = 16;
+        const AT_HWCAP2: usize = 26;
+        const WORD: usize = core::mem::size_of::<usize>();
+
+        // The auxiliary vector is read from procfs rather than through
+        // `getauxval`, whose declaration would need an `unsafe extern` block
+        // (Rust 1.82) to compile in edition 2024 crates
+        let auxv = std::fs::read("/proc/self/auxv").ok()?;
+        let word = |bytes: &[u8]| {
+            let mut word = [0; WORD];
+            word.copy_from_slice(bytes);
+            usize::from_ne_bytes(word)
+        };
+
+        let (mut hwcap, mut hwcap2) = (0, 0);
+        for entry in auxv.chunks_exact(2 * WORD) {
+            let (kind, value) = entry.split_at(WORD);
+            match word(kind) {
+                AT_HWCAP => hwcap = word(value),
+                AT_HWCAP2 => hwcap2 = word(value),
+                _ => {}
+            }
+        }
+        let bit = |register: usize, n: u32| register & (1 << n) != 0;
+
+        let detected = match feature {
+            "aes" => bit(hwcap, 3) && bit(hwcap, 4),
+            "crc" => bit(hwcap, 7),
+            "dotprod" => bit(hwcap, 20),
+            "fp" => bit(hwcap, 0),
+            "fp16" => bit(hwcap, 9) && bit(hwcap, 10),
+            "lse" => bit(hwcap, 8),
+            "neon" => bit(hwcap, 1),
+            "pmull" => bit(hwcap, 4),
+            "rdm" => bit(hwcap, 12),
+            "sha2" => bit(hwcap, 5) && bit(hwcap, 6),
+            "sha3" => bit(hwcap, 17) && bit(hwcap, 21),
+            "sve" => bit(hwcap, 22),
+            "sve2" => bit(hwcap2, 1),
+            _ => return None,
+        };
+
+        Some(detected)
+    }
+
+    /// Detects a CPU feature at runtime, returning `None` if it cannot be detected.
+    #[cfg(not(any(
+        target_arch = "x86",
+        target_arch = "x86_64",
+        all(
+            target_arch = "aarch64",
+            any(target_os = "linux", target_os = "android")
+        )
+    )))]
+    fn cpu_feature_detected(_feature: &str) -> Option<bool> {
+        None
+    }
+
+    /// Returns the Cargo features enabled for the build, sorted by name.
+    #[allow(dead_code)]
+    pub const fn enabled_features() -> &'static [&'static str] {
+        const LIST: &str = env_or_empty(decoded_env!("BUILD_FEATURES"));
+        const ITEMS: [&str; count_list_items(LIST, b',')] = split_list(LIST, b',');
+        &ITEMS
+    }
+
+    /// Returns `true` if the named Cargo feature was enabled for the build.
+    ///
+    /// Names use their `Cargo.toml` spelling, e.g. `has_feature("json-output")`.
+    #[allow(dead_code)]
+    pub const fn has_feature(name: &str) -> bool {
+        let features = Self::enabled_features();
+
+        let mut i = 0;
+        while i < features.len() {
+            if str_eq(features[i], name) {
+                return true;
+            }
+            i += 1;
+        }
+
+        false
+    }
+
+    /// Returns the effective rustflags of the build, if enabled in the build script.
+    ///
+    /// Path-like and secret-looking flags are replaced with `<redacted>`.
+    #[allow(dead_code)]
+    pub const fn rustflags() -> &'static [&'static str] {
+        const LIST: &str = env_or_empty(decoded_env!("BUILD_RUSTFLAGS"));
+        const ITEMS: [&str; count_list_items(LIST, b'\x1f')] = split_list(LIST, b'\x1f');
+        &ITEMS
+    }
+
+    /// Returns the Cargo profile the package was built with.
+    #[allow(dead_code)]
+    pub const fn build_profile() -> Option<BuildProfile> {
+        lookup(
+            decoded_env!("BUILD_PROFILE"),
+            &[
+                ("debug", BuildProfile::Debug),
+                ("release", BuildProfile::Release),
+            ],
+        )
+    }
+
+    /// Returns the optimization level the package was built with.
+    #[allow(dead_code)]
+    pub const fn opt_level() -> Option<OptLevel> {
+        lookup(
+            decoded_env!("BUILD_OPT_LEVEL"),
+            &[
+                ("0", OptLevel::O0),
+                ("1", OptLevel::O1),
+                ("2", OptLevel::O2),
+                ("3", OptLevel::O3),
+                ("s", OptLevel::Os),
+                ("z", OptLevel::Oz),
+            ],
+        )
+    }
+
+    /// Returns whether debug info was enabled for the build.
+    #[allow(dead_code)]
+    pub const fn debug_info() -> Option<bool> {
+        parse_bool(decoded_env!("BUILD_DEBUG"))
+    }
+
+    /// Returns the number of parallel jobs Cargo used for the build.
+    #[allow(dead_code)]
+    pub const fn num_jobs() -> Option<u32> {
+        const VALUE: Option<u32> = parse_u32(decoded_env!("BUILD_NUM_JOBS"));
+        VALUE
+    }
+
+    /// Returns the host triple of the machine that ran the build.
+    #[allow(dead_code)]
+    pub const fn host_triple() -> Option<&'static str> {
+        decoded_env!("BUILD_HOST_TRIPLE")
+    }
+
+    /// Returns the UTC build time as an `Option<u64>`.
+    #[allow(dead_code)]
+    pub const fn build_time_utc() -> Option<u64> {
+        const VALUE: Option<u64> = parse_u64(decoded_env!("BUILD_TIME_UTC"));
+        VALUE
+    }
+
+    /// Returns the UTC build time in milliseconds since the Unix epoch.
+    #[allow(dead_code)]
+    pub const fn build_time_utc_millis() -> Option<u64> {
+        const VALUE: Option<u64> = parse_u64(decoded_env!("BUILD_TIME_UTC_MILLIS"));
+        VALUE
+    }
+
+    /// Returns the UTC build time as an RFC 3339 timestamp (e.g. `2023-11-14T22:13:20Z`).
+    #[allow(dead_code)]
+    pub const fn build_time_rfc3339() -> Option<&'static str> {
+        decoded_env!("BUILD_TIME_RFC3339")
+    }
+
+    /// Returns the UTC build date as `YYYY-MM-DD`.
+    #[allow(dead_code)]
+    pub const fn build_date() -> Option<&'static str> {
+        decoded_env!("BUILD_DATE")
+    }
+
+    /// Returns where the build timestamp came from.
+    #[allow(dead_code)]
+    pub const fn build_time_source() -> Option<BuildTimeSource> {
+        lookup(
+            decoded_env!("BUILD_TIME_SOURCE"),
+            &[
+                ("source_date_epoch", BuildTimeSource::SourceDateEpoch),
+                ("git_commit", BuildTimeSource::GitCommit),
+                ("system_clock", BuildTimeSource::SystemClock),
+            ],
+        )
+    }
+
+    /// Returns the version of `rustc` used to build the package (e.g. `1.84.0`).
+    #[allow(dead_code)]
+    pub const fn rustc_version() -> Option<&'static str> {
+        decoded_env!("BUILD_RUSTC_VERSION")
+    }
+
+    /// Returns the version of `rustc` used to build the package, parsed into its components.
+    #[allow(dead_code)]
+    pub const fn rustc_version_parsed() -> Option<RustVersion> {
+        const VALUE: Option<RustVersion> = match decoded_env!("BUILD_RUSTC_VERSION") {
+            Some(version) => RustVersion::parse(version),
+            None => None,
+        };
+        VALUE
+    }
+
+    /// Returns `true` if the package was built with `rustc` `major.minor.patch` or newer.
+    ///
+    /// Returns `false` if the `rustc` version was not captured.
+    #[allow(dead_code)]
+    pub const fn built_with_rustc_at_least(major: u32, minor: u32, patch: u32) -> bool {
+        match Self::rustc_version_parsed() {
+            Some(version) => version.is_at_least(major, minor, patch),
+            None => false,
+        }
+    }
+
+    /// Returns the commit hash of the `rustc` used to build the package.
+    #[allow(dead_code)]
+    pub const fn rustc_commit_hash() -> Option<&'static str> {
+        decoded_env!("BUILD_RUSTC_COMMIT_HASH")
+    }
+
+    /// Returns the release channel of `rustc` (`stable`, `beta`, `nightly` or `dev`).
+    #[allow(dead_code)]
+    pub const fn rustc_channel() -> Option<&'static str> {
+        decoded_env!("BUILD_RUSTC_CHANNEL")
+    }
+
+    /// Returns the host triple of the `rustc` used to build the package.
+    #[allow(dead_code)]
+    pub const fn rustc_host() -> Option<&'static str> {
+        decoded_env!("BUILD_RUSTC_HOST")
+    }
+
+    /// Returns the LLVM version bundled with `rustc`.
+    #[allow(dead_code)]
+    pub const fn llvm_version() -> Option<&'static str> {
+        decoded_env!("BUILD_LLVM_VERSION")
+    }
+
+    /// Returns the version of Cargo used to build the package.
+    #[allow(dead_code)]
+    pub const fn cargo_version() -> Option<&'static str> {
+        decoded_env!("BUILD_CARGO_VERSION")
+    }
+
+    /// Returns the full git commit hash the package was built from.
+    #[allow(dead_code)]
+    pub const fn git_commit_hash() -> Option<&'static str> {
+        decoded_env!("BUILD_GIT_COMMIT_HASH")
+    }
+
+    /// Returns the abbreviated git commit hash the package was built from.
+    #[allow(dead_code)]
+    pub const fn git_commit_short() -> Option<&'static str> {
+        decoded_env!("BUILD_GIT_COMMIT_SHORT")
+    }
+
+    /// Returns the git branch, or `None` if built from a detached `HEAD`.
+    #[allow(dead_code)]
+    pub const fn git_branch() -> Option<&'static str> {
+        decoded_env!("BUILD_GIT_BRANCH")
+    }
+
+    /// Returns the git tag pointing at the built commit, if any.
+    #[allow(dead_code)]
+    pub const fn git_tag() -> Option<&'static str> {
+        decoded_env!("BUILD_GIT_TAG")
+    }
+
+    /// Returns whether the working tree had uncommitted changes at build time.
+    #[allow(dead_code)]
+    pub const fn git_dirty() -> Option<bool> {
+        parse_bool(decoded_env!("BUILD_GIT_DIRTY"))
+    }
+
+    /// Returns the commit time of the built commit as seconds since the Unix epoch.
+    #[allow(dead_code)]
+    pub const fn git_commit_time_utc() -> Option<u64> {
+        const VALUE: Option<u64> = parse_u64(decoded_env!("BUILD_GIT_COMMIT_TIME_UTC"));
+        VALUE
+    }
+
+    /// Returns the package's path relative to the repository root (empty for the root).
+    #[allow(dead_code)]
+    pub const fn git_path_in_vcs() -> Option<&'static str> {
+        decoded_env!("BUILD_GIT_PATH_IN_VCS")
+    }
+
+    /// Returns where the embedded VCS metadata came from.
+    #[allow(dead_code)]
+    pub const fn vcs_source() -> Option<VcsSource> {
+        lookup(
+            decoded_env!("BUILD_VCS_SOURCE"),
+            &[
+                ("git", VcsSource::Git),
+                ("cargo_vcs_info", VcsSource::CargoVcsInfo),
+                ("override", VcsSource::Override),
+            ],
+        )
+    }
+
+    /// Returns the hostname of the build machine (or its salted hash), if enabled
+    /// in the build script.
+    #[allow(dead_code)]
+    pub const fn build_host() -> Option<&'static str> {
+        decoded_env!("BUILD_HOST")
+    }
+
+    /// Returns the user that ran the build (or its salted hash), if enabled in
+    /// the build script.
+    #[allow(dead_code)]
+    pub const fn build_user() -> Option<&'static str> {
+        decoded_env!("BUILD_USER")
+    }
+
+    /// Returns the build-time value of an environment variable forwarded by
+    /// the build script's allow-list.
+    #[allow(dead_code)]
+    pub const fn build_env(name: &str) -> Option<&'static str> {
+        let entries = Self::build_env_entries();
+
+        let mut i = 0;
+        while i < entries.len() {
+            if let Some((var_name, value)) = split_entry(entries[i]) {
+                if str_eq(var_name, name) {
+                    return Some(value);
+                }
+            }
+            i += 1;
+        }
+
+        None
+    }
+
+    /// Returns every forwarded environment variable as `(name, value)` pairs,
+    /// sorted by name.
+    #[allow(dead_code)]
+    pub fn build_env_all() -> impl Iterator<Item = (&'static str, &'static str)> {
+        Self::build_env_entries()
+            .iter()
+            .filter_map(|entry| split_entry(entry))
+    }
+
+    /// Returns the forwarded environment variables as `NAME=value` entries.
+    #[allow(dead_code)]
+    const fn build_env_entries() -> &'static [&'static str] {
+        const LIST: &str = env_or_empty(decoded_env!("BUILD_ENV"));
+        const ITEMS: [&str; count_list_items(LIST, b'\x1f')] = split_list(LIST, b'\x1f');
+        &ITEMS
+    }
+
+    /// Returns the CI service that ran the build, or `None` for local builds.
+    #[allow(dead_code)]
+    pub const fn ci_provider() -> Option<CiProvider> {
+        lookup(
+            decoded_env!("BUILD_CI_PROVIDER"),
+            &[
+                ("github_actions", CiProvider::GitHubActions),
+                ("gitlab_ci", CiProvider::GitLabCi),
+                ("buildkite", CiProvider::Buildkite),
+                ("circleci", CiProvider::CircleCi),
+                ("jenkins", CiProvider::Jenkins),
+                ("travis_ci", CiProvider::TravisCi),
+                ("azure_pipelines", CiProvider::AzurePipelines),
+                ("bitbucket_pipelines", CiProvider::BitbucketPipelines),
+                ("teamcity", CiProvider::TeamCity),
+                ("generic", CiProvider::Generic),
+            ],
+        )
+    }
+
+    /// Returns the CI run, pipeline or build number.
+    #[allow(dead_code)]
+    pub const fn ci_build_id() -> Option<&'static str> {
+        decoded_env!("BUILD_CI_BUILD_ID")
+    }
+
+    /// Returns a link to the CI build.
+    #[allow(dead_code)]
+    pub const fn ci_build_url() -> Option<&'static str> {
+        decoded_env!("BUILD_CI_BUILD_URL")
+    }
+
+    /// Returns the name of the CI job that ran the build.
+    #[allow(dead_code)]
+    pub const fn ci_job_name() -> Option<&'static str> {
+        decoded_env!("BUILD_CI_JOB_NAME")
+    }
+
+    /// Decodes a value set by `set_cargo_env_var`, such as the result of
+    /// `option_env!("MY_CUSTOM_VAR")`.
+    ///
+    /// Line feeds and carriage returns are written as `\n` and `\r`, and
+    /// backslashes are doubled, so any value survives the round trip unchanged
+    /// (including Windows paths and CRLF line endings).
+    #[allow(dead_code)]
+    pub fn decode_env_value(encoded: &str) -> String {
+        let mut decoded = String::with_capacity(encoded.len());
+        let mut chars = encoded.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                decoded.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('\\') => decoded.push('\\'),
+                Some('n') => decoded.push('\n'),
+                Some('r') => decoded.push('\r'),
+                Some(other) => {
+                    decoded.push('\\');
+                    decoded.push(other);
+                }
+                None => decoded.push('\\'),
+            }
+        }
+
+        decoded
+    }
+
+    /// Splits a multi-line environment variable into a vector of lines.
+    ///
+    /// This function takes the output of `option_env!()` (i.e., `Option<&'static str>`)
+    /// and performs the following steps:
+    ///
+    /// 1. If `env_data` is `None`, it returns `None`.
+    /// 2. If `env_data` is `Some(...)`, it first applies `decode_env_value(...)`
+    ///    to restore the original line breaks.
+    /// 3. The decoded string is then split into lines using `.lines()`.
+    /// 4. Finally, it returns `Some(Vec<&'static str>)` where each element represents
+    ///    a line from the multi-line environment variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `env_data` - An `Option<&'static str>` containing the raw environment variable data.
+    ///   - This is typically the result of `option_env!("VAR_NAME")`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Vec<&'static str>)` if the environment variable exists and contains multiple lines.
+    /// * `None` if the environment variable is not set.
+    ///
+    /// Variables registered with `BuildMetadataInjector::set_multi_line_custom_var`
+    /// also get a `<NAME>_LINES` constant, which needs no allocation.
+    #[allow(dead_code)]
+    pub fn split_multi_line_custom_var(env_data: Option<&'static str>) -> Option<Vec<String>> {
+        env_data.map(|data| {
+            let decoded = Self::decode_env_value(data);
+            decoded.lines().map(String::from).collect() // Convert each line into an owned String
+        })
+    }
+}
+
+/// The package metadata as a `const`, usable in const contexts, statics and
+/// compile-time assertions.
+#[allow(dead_code)]
+pub(crate) const INFO: PkgInfo = PkgInfo {
+    name: env_or_empty(CargoPkgInfo::pkg_name()),
+    version: Version {
+        major: match CargoPkgInfo::version_major_numeric() {
+            Some(major) => major,
+            None => 0,
+        },
+        minor: match CargoPkgInfo::version_minor_numeric() {
+            Some(minor) => minor,
+            None => 0,
+        },
+        patch: match CargoPkgInfo::version_patch_numeric() {
+            Some(patch) => patch,
+            None => 0,
+        },
+        pre: env_or_empty(CargoPkgInfo::version_pre()),
+    },
+    authors: env_or_empty(CargoPkgInfo::authors()),
+    description: env_or_empty(CargoPkgInfo::description()),
+    homepage: env_or_empty(CargoPkgInfo::homepage()),
+    repository: env_or_empty(CargoPkgInfo::repository()),
+    license: env_or_empty(CargoPkgInfo::license()),
+    license_content: CargoPkgInfo::license_content(),
+    rust_version: CargoPkgInfo::rust_version_parsed(),
+    build_target: CargoPkgInfo::build_target(),
+    build_time_utc: CargoPkgInfo::build_time_utc(),
+    build_profile: CargoPkgInfo::build_profile(),
+    git_commit_hash: CargoPkgInfo::git_commit_hash(),
+    rustc_version: CargoPkgInfo::rustc_version_parsed(),
+};
+
+impl CargoPkgInfo {
+    /// Returns the contents of the license file (embedded at build time).
+    #[allow(dead_code)]
+    pub const fn license_content() -> Option<&'static str> {
+        Some(include_str!("internal_pkg_info_license.txt"))
+    }
+}
+
+/// Accessors for the custom variables registered in the build script.
+#[allow(dead_code)]
+pub(crate) mod custom {
+    /// Returns the value of `INTERNAL_CUSTOM_ENV_VAR`.
+    pub const fn internal_custom_env_var() -> &'static str {
+        "INTERNAL_RESULT"
+    }
+}
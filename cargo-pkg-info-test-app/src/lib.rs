@@ -1,2 +1,16 @@
 pub mod cargo_pkg_info;
-pub use cargo_pkg_info::CargoPkgInfo;
+mod internal_pkg_info;
+pub use cargo_pkg_info::{
+    custom, BuildProfile, CargoPkgInfo, CiProvider, Endian, OptLevel, PkgInfo, RustVersion,
+    TargetArch, TargetInfo, TargetOs, VcsSource, Version, INFO,
+};
+
+/// Returns the package name read through the crate-private generated file.
+pub fn internal_pkg_name() -> Option<&'static str> {
+    internal_pkg_info::CargoPkgInfo::pkg_name()
+}
+
+/// Returns a custom variable registered only for the crate-private generated file.
+pub fn internal_custom_var() -> &'static str {
+    internal_pkg_info::custom::internal_custom_env_var()
+}
@@ -1,4 +1,8 @@
-use cargo_pkg_info_test_app::CargoPkgInfo;
+use cargo_pkg_info_runtime::PackageInfo;
+use cargo_pkg_info_test_app::{
+    custom, internal_custom_var, internal_pkg_name, BuildProfile, CargoPkgInfo, CiProvider, Endian,
+    OptLevel, PkgInfo, RustVersion, TargetArch, TargetOs, VcsSource, Version, INFO,
+};
 
 #[test]
 fn test_pkg_info() {
@@ -159,6 +163,320 @@ fn test_pkg_info() {
         CargoPkgInfo::build_time_utc().is_some(),
         "Expected build time to be available, but it was None"
     );
+    assert!(
+        CargoPkgInfo::build_time_source().is_some(),
+        "Expected build time source to be available, but it was None"
+    );
+
+    // Formatted timestamps are derived from the same build time
+    let build_time_utc = CargoPkgInfo::build_time_utc().unwrap();
+    assert_eq!(
+        CargoPkgInfo::build_time_utc_millis().map(|millis| millis / 1000),
+        Some(build_time_utc)
+    );
+    let build_time_rfc3339 = CargoPkgInfo::build_time_rfc3339().unwrap();
+    let build_date = CargoPkgInfo::build_date().unwrap();
+    assert_eq!(build_date.len(), "YYYY-MM-DD".len());
+    assert!(
+        build_time_rfc3339.starts_with(build_date) && build_time_rfc3339.ends_with('Z'),
+        "Unexpected RFC 3339 timestamp '{}' for date '{}'",
+        build_time_rfc3339,
+        build_date
+    );
+}
+
+#[test]
+fn test_license_content_is_const() {
+    // Embedded with `include_str!`, so usable in const contexts
+    const LICENSE_CONTENT: Option<&str> = CargoPkgInfo::license_content();
+
+    assert!(LICENSE_CONTENT.is_some_and(|content| content.starts_with("=== MOCK LICENSE")));
+    assert_eq!(LICENSE_CONTENT, CargoPkgInfo::license_content());
+}
+
+// Checked at compile time
+const _: () = assert!(INFO.version.major == 1 && INFO.version.minor == 2);
+
+#[test]
+fn test_const_info() {
+    const PKG_INFO: PkgInfo = INFO;
+    static VERSIONS: [Version; 1] = [INFO.version];
+
+    assert_eq!(PKG_INFO.name, "cargo-pkg-info-test-app");
+    assert_eq!(
+        VERSIONS[0],
+        Version {
+            major: 1,
+            minor: 2,
+            patch: 3,
+            pre: "beta",
+        }
+    );
+    assert_eq!(PKG_INFO.authors, CargoPkgInfo::authors().unwrap());
+    assert_eq!(PKG_INFO.license, "MIT");
+    assert_eq!(PKG_INFO.license_content, CargoPkgInfo::license_content());
+    assert_eq!(
+        PKG_INFO.rust_version,
+        Some(RustVersion {
+            major: 1,
+            minor: 84,
+            patch: 0,
+        })
+    );
+    assert_eq!(PKG_INFO.build_target, CargoPkgInfo::build_target());
+    assert_eq!(PKG_INFO.build_time_utc, CargoPkgInfo::build_time_utc());
+    assert_eq!(PKG_INFO.build_profile, CargoPkgInfo::build_profile());
+    assert_eq!(PKG_INFO.git_commit_hash, CargoPkgInfo::git_commit_hash());
+    assert_eq!(PKG_INFO.rustc_version, CargoPkgInfo::rustc_version_parsed());
+}
+
+#[test]
+fn test_const_accessors() {
+    const TARGET_OS: Option<TargetOs> = match CargoPkgInfo::target_info() {
+        Some(target) => Some(target.os),
+        None => None,
+    };
+    const VERSION_MAJOR: Option<&str> = CargoPkgInfo::build_env("CARGO_PKG_VERSION_MAJOR");
+
+    assert_eq!(
+        TARGET_OS,
+        CargoPkgInfo::target_info().map(|target| target.os)
+    );
+    const { assert!(CargoPkgInfo::has_feature("json-output")) };
+    assert_eq!(VERSION_MAJOR, Some("1"));
+    const { assert!(CargoPkgInfo::built_with_rustc_at_least(1, 84, 0)) };
+}
+
+#[test]
+fn test_target_info() {
+    let target = CargoPkgInfo::target_info().expect("Target info should be captured");
+
+    // The tests run on the target they were built for
+    assert_eq!(
+        target.arch == TargetArch::X86_64,
+        cfg!(target_arch = "x86_64")
+    );
+    assert_eq!(
+        target.arch == TargetArch::Aarch64,
+        cfg!(target_arch = "aarch64")
+    );
+    assert_eq!(target.os == TargetOs::Linux, cfg!(target_os = "linux"));
+    assert_eq!(target.os == TargetOs::Windows, cfg!(target_os = "windows"));
+    assert_eq!(target.os == TargetOs::MacOs, cfg!(target_os = "macos"));
+    assert_eq!(
+        target.endian == Endian::Little,
+        cfg!(target_endian = "little")
+    );
+    assert_eq!(target.pointer_width, usize::BITS);
+
+    assert_eq!(CargoPkgInfo::target_family().contains(&"unix"), cfg!(unix));
+    assert_eq!(
+        CargoPkgInfo::target_family().contains(&"windows"),
+        cfg!(windows)
+    );
+    assert_eq!(target.family, CargoPkgInfo::target_family());
+    assert!(CargoPkgInfo::target_has_atomic()
+        .iter()
+        .all(|width| !width.is_empty()));
+    assert_eq!(
+        CargoPkgInfo::target_has_atomic().contains(&"ptr"),
+        cfg!(target_has_atomic = "ptr")
+    );
+}
+
+#[test]
+fn test_cpu_features() {
+    assert_eq!(
+        CargoPkgInfo::target_features().contains(&"sse2"),
+        cfg!(target_feature = "sse2")
+    );
+
+    let compiled = ["sse2", "avx2", "tbm", "made-up"];
+    let detect = |feature: &str| match feature {
+        "sse2" => Some(true),
+        "avx2" | "tbm" => Some(false),
+        _ => None,
+    };
+
+    // Undetectable features are assumed to be present
+    assert_eq!(
+        CargoPkgInfo::missing_cpu_features(&compiled, detect),
+        ["avx2", "tbm"]
+    );
+    assert!(CargoPkgInfo::missing_cpu_features(&compiled, |_| Some(true)).is_empty());
+    assert!(CargoPkgInfo::missing_cpu_features(&[], |_| Some(false)).is_empty());
+}
+
+#[test]
+fn test_check_cpu_features_on_host() {
+    // This test runs, so the host supports every feature it was compiled for,
+    // including baseline ones such as `sse2` on x86_64, which are detected on
+    // the CPU itself
+    assert_eq!(CargoPkgInfo::check_cpu_features(), Vec::<&str>::new());
+}
+
+#[test]
+fn test_rustflags() {
+    let rustflags = CargoPkgInfo::rustflags();
+
+    assert!(rustflags.iter().all(|flag| !flag.is_empty()));
+    assert!(
+        rustflags.iter().all(|flag| !flag.contains('/')),
+        "Path-like flags should be redacted: {:?}",
+        rustflags
+    );
+}
+
+#[test]
+fn test_enabled_features() {
+    // Tests are built with the default features only
+    assert_eq!(
+        CargoPkgInfo::enabled_features(),
+        &["default", "json-output"]
+    );
+    assert!(CargoPkgInfo::has_feature("json-output"));
+    assert!(!CargoPkgInfo::has_feature("json_output"));
+    assert!(!CargoPkgInfo::has_feature("extra_logging"));
+}
+
+#[test]
+fn test_build_profile() {
+    // Tests run with the default `dev` or `release` profile settings
+    if cfg!(debug_assertions) {
+        assert_eq!(CargoPkgInfo::build_profile(), Some(BuildProfile::Debug));
+        assert_eq!(CargoPkgInfo::opt_level(), Some(OptLevel::O0));
+        assert_eq!(CargoPkgInfo::debug_info(), Some(true));
+    } else {
+        assert_eq!(CargoPkgInfo::build_profile(), Some(BuildProfile::Release));
+        assert_eq!(CargoPkgInfo::opt_level(), Some(OptLevel::O3));
+    }
+
+    assert!(CargoPkgInfo::num_jobs().is_some_and(|jobs| jobs > 0));
+    assert_eq!(CargoPkgInfo::host_triple(), CargoPkgInfo::rustc_host());
+}
+
+#[test]
+fn test_toolchain_info() {
+    let rustc_version = CargoPkgInfo::rustc_version().expect("rustc version should be captured");
+    assert!(
+        rustc_version.starts_with("1."),
+        "Unexpected rustc version: {}",
+        rustc_version
+    );
+    assert!(matches!(
+        CargoPkgInfo::rustc_channel(),
+        Some("stable" | "beta" | "nightly" | "dev")
+    ));
+    assert!(CargoPkgInfo::rustc_host().is_some());
+    assert!(CargoPkgInfo::cargo_version().is_some());
+}
+
+#[test]
+fn test_rust_version_parsed() {
+    assert_eq!(
+        CargoPkgInfo::rust_version_parsed(),
+        Some(RustVersion {
+            major: 1,
+            minor: 84,
+            patch: 0
+        })
+    );
+
+    // The build script enforces the MSRV, so the compiler must satisfy it
+    let rustc_version = CargoPkgInfo::rustc_version_parsed().expect("rustc version should parse");
+    assert!(Some(rustc_version) >= CargoPkgInfo::rust_version_parsed());
+    assert!(CargoPkgInfo::built_with_rustc_at_least(1, 84, 0));
+    assert!(!CargoPkgInfo::built_with_rustc_at_least(
+        rustc_version.major + 1,
+        0,
+        0
+    ));
+}
+
+#[test]
+fn test_ci_info() {
+    // Tests run in the environment they were built in
+    if std::env::var("GITHUB_ACTIONS").is_ok_and(|value| !value.is_empty()) {
+        assert_eq!(CargoPkgInfo::ci_provider(), Some(CiProvider::GitHubActions));
+        assert_eq!(
+            CargoPkgInfo::ci_build_id(),
+            std::env::var("GITHUB_RUN_ID").ok().as_deref()
+        );
+    } else if std::env::var("CI").is_err() {
+        assert_eq!(CargoPkgInfo::ci_provider(), None);
+        assert_eq!(CargoPkgInfo::ci_build_id(), None);
+        assert_eq!(CargoPkgInfo::ci_build_url(), None);
+        assert_eq!(CargoPkgInfo::ci_job_name(), None);
+    }
+}
+
+#[test]
+fn test_build_host_omitted() {
+    // The build script does not opt in to capturing the build host
+    assert_eq!(CargoPkgInfo::build_host(), None);
+    assert_eq!(CargoPkgInfo::build_user(), None);
+}
+
+#[test]
+fn test_build_env() {
+    // Cargo sets the package version variables for build scripts too
+    assert_eq!(
+        CargoPkgInfo::build_env("CARGO_PKG_VERSION_MAJOR"),
+        Some("1")
+    );
+    assert_eq!(
+        CargoPkgInfo::build_env("CARGO_PKG_VERSION_PRE"),
+        Some("beta")
+    );
+    assert_eq!(CargoPkgInfo::build_env("CARGO_PKG_NAME"), None);
+
+    let names: Vec<&str> = CargoPkgInfo::build_env_all()
+        .map(|(name, _)| name)
+        .filter(|name| name.starts_with("CARGO_"))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "CARGO_PKG_VERSION_MAJOR",
+            "CARGO_PKG_VERSION_MINOR",
+            "CARGO_PKG_VERSION_PATCH",
+            "CARGO_PKG_VERSION_PRE"
+        ]
+    );
+    assert_eq!(
+        CargoPkgInfo::build_env("DEPLOY_ENV"),
+        std::env::var("DEPLOY_ENV").ok().as_deref()
+    );
+}
+
+#[test]
+fn test_git_info() {
+    // Builds from a source archive have no repository, so only check consistency
+    if let Some(commit_hash) = CargoPkgInfo::git_commit_hash() {
+        assert_eq!(
+            commit_hash.len(),
+            40,
+            "Unexpected commit hash: {}",
+            commit_hash
+        );
+
+        let commit_short = CargoPkgInfo::git_commit_short().expect("Short hash should be set");
+        assert!(
+            commit_hash.starts_with(commit_short),
+            "Expected '{}' to be a prefix of '{}'",
+            commit_short,
+            commit_hash
+        );
+
+        // The test app lives in the `cargo-pkg-info-test-app` directory of this repository
+        assert_eq!(CargoPkgInfo::vcs_source(), Some(VcsSource::Git));
+        assert_eq!(
+            CargoPkgInfo::git_path_in_vcs(),
+            Some("cargo-pkg-info-test-app")
+        );
+    } else {
+        assert_eq!(CargoPkgInfo::git_commit_short(), None);
+    }
 }
 
 #[test]
@@ -178,3 +496,90 @@ fn test_custom_vars() {
         ])
     );
 }
+
+#[test]
+fn test_custom_var_round_trip() {
+    // Backslashes, line breaks and non-ASCII text survive `cargo:rustc-env`
+    assert_eq!(
+        CargoPkgInfo::decode_env_value(env!("TEST_ROUND_TRIP_VAR")),
+        "C:\\new\\raw = é ✓\r\nline2\\n"
+    );
+    assert_eq!(
+        custom::test_round_trip_var(),
+        "C:\\new\\raw = é ✓\r\nline2\\n"
+    );
+}
+
+#[test]
+fn test_custom_values() {
+    // Usable in const contexts, since no runtime parsing is involved
+    const BUFFER: [u8; CargoPkgInfo::MAX_CONNECTIONS as usize] =
+        [0; CargoPkgInfo::MAX_CONNECTIONS as usize];
+    assert_eq!(BUFFER.len(), 64);
+
+    let max_connections: u32 = CargoPkgInfo::MAX_CONNECTIONS;
+    assert_eq!(max_connections, 64);
+    assert_eq!(CargoPkgInfo::RETRY_OFFSET, -3i64);
+    const _: () = assert!(CargoPkgInfo::ENABLE_METRICS);
+    assert_eq!(CargoPkgInfo::SAMPLE_RATE, 0.25f64);
+    assert_eq!(CargoPkgInfo::REGIONS, &["eu-west", "us-east"]);
+    assert_eq!(
+        CargoPkgInfo::TIMEOUTS_MS,
+        &[("connect", 500u64), ("read", 2_000u64)]
+    );
+}
+
+#[test]
+fn test_custom_var_accessors() {
+    assert_eq!(custom::test_custom_env_var(), "TEST_RESULT");
+    assert_eq!(
+        custom::test_multi_line_custom_env_var_lines(),
+        &[
+            "Some multi-line environment variable",
+            "",
+            "Level 1",
+            "    Level 2",
+            "        Level 3",
+        ]
+    );
+    assert_eq!(
+        custom::test_multi_line_custom_env_var(),
+        custom::test_multi_line_custom_env_var_lines().join("\n")
+    );
+}
+
+#[test]
+fn test_custom_var_lines_const() {
+    // Usable in const contexts without allocating
+    const LINES: &[&str] = CargoPkgInfo::TEST_MULTI_LINE_CUSTOM_ENV_VAR_LINES;
+    const LINE_COUNT: usize = LINES.len();
+
+    assert_eq!(LINE_COUNT, 5);
+    assert_eq!(LINES[2], "Level 1");
+    assert_eq!(LINES, custom::test_multi_line_custom_env_var_lines());
+}
+
+#[test]
+fn test_internal_pkg_info() {
+    // Generated a second time with `pub(crate)` visibility in the same crate
+    assert_eq!(internal_pkg_name(), CargoPkgInfo::pkg_name());
+    assert_eq!(internal_custom_var(), "INTERNAL_RESULT");
+}
+
+/// A helper written once against the trait, as a shared library would be.
+fn startup_banner(info: &dyn PackageInfo) -> String {
+    format!("{} v{} ({})", info.name(), info.version(), info.authors())
+}
+
+#[test]
+fn test_package_info_trait() {
+    let info = CargoPkgInfo {};
+
+    assert_eq!(
+        startup_banner(&info),
+        "cargo-pkg-info-test-app v1.2.3-beta (Test Author 1 <test1@example.com>:Test Author 2 <test2@example.com>)"
+    );
+    assert_eq!(info.license(), Some("MIT"));
+    assert_eq!(info.target(), CargoPkgInfo::build_target());
+    assert_eq!(info.build_time_utc(), CargoPkgInfo::build_time_utc());
+}